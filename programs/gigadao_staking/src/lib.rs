@@ -2,13 +2,15 @@ mod utils;
 use utils::*;
 use std::str::FromStr;
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer};
 use anchor_lang::solana_program::{
     program::invoke_signed,
     system_instruction::create_account,
 };
 use anchor_lang::solana_program::system_instruction::transfer;
 use anchor_lang::solana_program::program::invoke;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
 use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
 
@@ -25,13 +27,35 @@ const DAO_AUTH_PDA_SEED: &[u8] = b"dao_auth_pda_seed";
 const STAKE_AUTH_PDA_SEED: &[u8] = b"stake_auth_pda_seed";
 const STAKE_PDA_SEED: &[u8] = b"stake_pda_seed";
 const CONNECTION_PDA_SEED: &[u8] = b"connection_pda_seed";
+const PROPOSAL_PDA_SEED: &[u8] = b"proposal_pda_seed";
 const METADATA_PREFIX: &[u8] = b"metadata";
 const FEE_CONTROLLER_PDA_SEED: &[u8] = b"fee_controller";
+const FEE_OVERRIDE_PDA_SEED: &[u8] = b"fee_override_pda_seed";
+const FEE_VAULT_PDA_SEED: &[u8] = b"fee_vault_pda_seed";
+const GLOBAL_CONFIG_PDA_SEED: &[u8] = b"global_config";
+const TREASURY_PDA_SEED: &[u8] = b"treasury_pda_seed";
+const STREAM_PDA_SEED: &[u8] = b"stream_pda_seed";
+const DAO_PDA_SEED: &[u8] = b"dao_pda_seed";
 
 // consts
+// hard ceilings, the global config can only tighten these
 const MAX_DECIMALS: u8 = 12;
 const MAX_STREAM_RATE: u64 = 7e9 as u64; // assuming minimum 1 month runway and 10k connections
 const MAX_CONNECTIONS_PER_STREAM: u64 = 2e4 as u64;
+const DEFAULT_FEE_LAMPORTS: u64 = 666;
+const BPS_DENOMINATOR: u64 = 10_000;
+const MAX_FEE_OVERRIDE_BPS: u32 = 100_000; // surcharges capped at 10x the global fee
+const MAX_CLAIM_FEE_BPS: u16 = 1_000;
+const DEFAULT_FEE_INCREASE_DELAY_SECONDS: u64 = 7 * 24 * 60 * 60;
+
+// councillor role permission bits
+const PERMISSION_INITIALIZE_STREAM: u16 = 1 << 0;
+const PERMISSION_REACTIVATE_STREAM: u16 = 1 << 1;
+const PERMISSION_PROPOSE: u16 = 1 << 2;
+const PERMISSION_VOTE_GOVERNANCE: u16 = 1 << 3; // multisig, config, roles and custom instructions
+const PERMISSION_VOTE_TREASURY: u16 = 1 << 4; // withdrawals and stream closure
+const PERMISSION_VOTE_STREAMS: u16 = 1 << 5; // stream rate, creators and deactivation
+const ALL_PERMISSIONS: u16 = (1 << 6) - 1;
 
 #[program]
 pub mod gigadao_staking {
@@ -41,96 +65,599 @@ pub mod gigadao_staking {
     // system-wide config
     pub fn initialize_fee_controller(
         ctx: Context<InitializeFeeController>,
+        fee_authorities: Vec<Pubkey>,
+        fee_approval_threshold: u64,
     ) -> ProgramResult {
+
+        // the hard-coded key is only used to bootstrap the controller, after that the stored authority governs it
         let fee_mod_address: Pubkey = Pubkey::from_str(FEE_MOD_ADDRESS).unwrap();
         if ctx.accounts.signer.key() != fee_mod_address{
             return Err(ErrorCode::InvalidFeeModAddress.into());
         }
+        validate_authority_config(&fee_authorities, fee_approval_threshold)?;
+
+        let fee_controller = &mut ctx.accounts.fee_controller;
+        fee_controller.fee_authorities = fee_authorities;
+        fee_controller.fee_approval_threshold = fee_approval_threshold;
+        fee_controller.pending_fee_authorities = Vec::new();
+        fee_controller.pending_fee_approval_threshold = 0;
+        fee_controller.fee_receivers = vec![FeeReceiver {
+            address: Pubkey::from_str(FEE_RX_ADDRESS).unwrap(),
+            share_bps: BPS_DENOMINATOR as u16,
+        }];
+        fee_controller.fees = vec![DEFAULT_FEE_LAMPORTS; MAX_INSTRUCTIONS];
+        fee_controller.fee_increase_delay_seconds = DEFAULT_FEE_INCREASE_DELAY_SECONDS;
+        fee_controller.pending_fees = Vec::new();
+        fee_controller.pending_token_fees = Vec::new();
+        fee_controller.pending_fees_effective_timestamp = 0;
+        fee_controller.pending_fee_increase_delay_seconds = None;
+        fee_controller.pending_claim_fee_bps = None;
+        fee_controller.pending_fee_mint = None;
+        fee_controller.fee_mint = None;
+        fee_controller.token_fees = vec![0; MAX_INSTRUCTIONS];
+        fee_controller.fees_collected = vec![0; MAX_INSTRUCTIONS];
+        fee_controller.token_fees_collected = vec![0; MAX_INSTRUCTIONS];
+        fee_controller.fee_tx_count = vec![0; MAX_INSTRUCTIONS];
+        fee_controller.fee_stats_reset_timestamp = Clock::get().unwrap().unix_timestamp as u64;
+        fee_controller.fee_exemptions = Vec::new();
+        fee_controller.claim_fee_bps = 0;
+        fee_controller.protocol_fee_owner = Pubkey::from_str(FEE_RX_ADDRESS).unwrap();
+        Ok(())
+    }
+
+    pub fn propose_fee_authority(
+        ctx: Context<ProposeFeeAuthority>,
+        new_fee_authorities: Vec<Pubkey>,
+        new_fee_approval_threshold: u64,
+    ) -> ProgramResult {
+
+        // validate
+        let fee_controller = &ctx.accounts.fee_controller;
+        validate_authority_signers(
+            &fee_controller.fee_authorities,
+            fee_controller.fee_approval_threshold,
+            &ctx.accounts.signer,
+            ctx.remaining_accounts,
+        )?;
+        validate_authority_config(&new_fee_authorities, new_fee_approval_threshold)?;
+
+        // stage handover, takes effect once the new authority accepts
+        let fee_controller = &mut ctx.accounts.fee_controller;
+        fee_controller.pending_fee_authorities = new_fee_authorities;
+        fee_controller.pending_fee_approval_threshold = new_fee_approval_threshold;
+
+        Ok(())
+    }
+
+    pub fn accept_fee_authority(
+        ctx: Context<AcceptFeeAuthority>,
+    ) -> ProgramResult {
+
+        // validate that the pending authority controls its keys
+        let fee_controller = &ctx.accounts.fee_controller;
+        if fee_controller.pending_fee_authorities.is_empty() {
+            return Err(ErrorCode::NoPendingFeeAuthority.into());
+        }
+        validate_authority_signers(
+            &fee_controller.pending_fee_authorities,
+            fee_controller.pending_fee_approval_threshold,
+            &ctx.accounts.signer,
+            ctx.remaining_accounts,
+        )?;
+
+        // finalize handover
         let fee_controller = &mut ctx.accounts.fee_controller;
-        fee_controller.initialize_dao = 666;
-        fee_controller.initialize_stream = 666;
-        fee_controller.reactivate_stream = 666;
-        fee_controller.propose_dao_command = 666;
-        fee_controller.approve_dao_command = 666;
-        fee_controller.execute_update_dao_multisig = 666;
-        fee_controller.execute_deactivate_stream = 666;
-        fee_controller.execute_withdraw_from_stream = 666;
-        fee_controller.initialize_stake = 666;
-        fee_controller.stake_nft = 666;
-        fee_controller.unstake_nft = 666;
-        fee_controller.initialize_connection = 666;
-        fee_controller.connect_to_stream = 666;
-        fee_controller.claim_from_stream = 666;
-        fee_controller.disconnect_from_stream = 666;
+        fee_controller.fee_authorities = fee_controller.pending_fee_authorities.clone();
+        fee_controller.fee_approval_threshold = fee_controller.pending_fee_approval_threshold;
+        fee_controller.pending_fee_authorities = Vec::new();
+        fee_controller.pending_fee_approval_threshold = 0;
+
         Ok(())
     }
 
     pub fn update_fee_controller(
         ctx: Context<UpdateFeeController>,
-        instruction_name: String,
+        fee_instruction_int: u8,
         new_fee_amount_lamports: u64,
+        new_fee_schedule: Option<Vec<u64>>,
     ) -> ProgramResult {
-        msg!("Updating {:?} to {:?}", instruction_name, new_fee_amount_lamports);
-        let fee_mod_address: Pubkey = Pubkey::from_str(FEE_MOD_ADDRESS).unwrap();
-        if ctx.accounts.signer.key() != fee_mod_address{
-            return Err(ErrorCode::InvalidFeeModAddress.into());
+        let fee_controller = &ctx.accounts.fee_controller;
+        validate_authority_signers(
+            &fee_controller.fee_authorities,
+            fee_controller.fee_approval_threshold,
+            &ctx.accounts.signer,
+            ctx.remaining_accounts,
+        )?;
+
+        // convert fee instruction
+        let fee_instruction: FeeInstruction = FromPrimitive::from_u8(fee_instruction_int).ok_or(ErrorCode::InvalidFeeInstruction)?;
+
+        // build on any schedule already pending so consecutive updates accumulate
+        let fee_controller: &mut FeeController = &mut ctx.accounts.fee_controller;
+        let mut new_fees = if fee_controller.pending_fees.is_empty() {
+            fee_controller.fees.clone()
+        } else {
+            fee_controller.pending_fees.clone()
+        };
+
+        // replace the full schedule first so that the single update below always wins,
+        // it must cover every instruction so a truncated list can't zero out fees
+        if let Some(mut new_fee_schedule) = new_fee_schedule {
+            if new_fee_schedule.len() != NUM_FEE_INSTRUCTIONS {
+                return Err(ErrorCode::InvalidFeeSchedule.into());
+            }
+            new_fee_schedule.resize(MAX_INSTRUCTIONS, 0);
+            new_fees = new_fee_schedule;
+            msg!("Replaced fee schedule");
+        }
+        new_fees.resize(MAX_INSTRUCTIONS, 0);
+        new_fees[fee_instruction as usize] = new_fee_amount_lamports;
+
+        // decreases apply now, increases wait out the delay
+        let has_increase = stage_fee_schedule(&mut fee_controller.fees, &mut fee_controller.pending_fees, new_fees);
+        if has_increase {
+            let current_timestamp = Clock::get().unwrap().unix_timestamp as u64;
+            fee_controller.pending_fees_effective_timestamp = current_timestamp + fee_controller.fee_increase_delay_seconds;
+            msg!("Fee increases pending until {:?}", fee_controller.pending_fees_effective_timestamp);
+        }
+        msg!("Updated {:?} to {:?}", fee_instruction, new_fee_amount_lamports);
+        Ok(())
+    }
+
+    pub fn update_fee_increase_delay(
+        ctx: Context<UpdateFeeIncreaseDelay>,
+        fee_increase_delay_seconds: u64,
+    ) -> ProgramResult {
+
+        // validate
+        let fee_controller = &ctx.accounts.fee_controller;
+        validate_authority_signers(
+            &fee_controller.fee_authorities,
+            fee_controller.fee_approval_threshold,
+            &ctx.accounts.signer,
+            ctx.remaining_accounts,
+        )?;
+
+        // lengthening applies now, shortening waits out the current delay so it can't be used to rush an increase,
+        // already pending increases keep their effective timestamp
+        let fee_controller = &mut ctx.accounts.fee_controller;
+        if fee_increase_delay_seconds >= fee_controller.fee_increase_delay_seconds {
+            fee_controller.fee_increase_delay_seconds = fee_increase_delay_seconds;
+            fee_controller.pending_fee_increase_delay_seconds = None;
+        } else {
+            let current_timestamp = Clock::get().unwrap().unix_timestamp as u64;
+            fee_controller.pending_fee_increase_delay_seconds = Some(fee_increase_delay_seconds);
+            fee_controller.pending_fees_effective_timestamp = current_timestamp + fee_controller.fee_increase_delay_seconds;
+            msg!("Fee increase delay decrease pending until {:?}", fee_controller.pending_fees_effective_timestamp);
+        }
+
+        Ok(())
+    }
+
+    pub fn apply_pending_fees(
+        ctx: Context<ApplyPendingFees>,
+    ) -> ProgramResult {
+
+        // permissionless, only checks that the schedule is due
+        let fee_controller = &mut ctx.accounts.fee_controller;
+        if fee_controller.pending_fees.is_empty()
+            && fee_controller.pending_token_fees.is_empty()
+            && fee_controller.pending_fee_increase_delay_seconds.is_none()
+            && fee_controller.pending_claim_fee_bps.is_none()
+            && fee_controller.pending_fee_mint.is_none() {
+            return Err(ErrorCode::NoPendingFees.into());
+        }
+        let current_timestamp = Clock::get().unwrap().unix_timestamp as u64;
+        if current_timestamp < fee_controller.pending_fees_effective_timestamp {
+            return Err(ErrorCode::PendingFeesNotDue.into());
+        }
+
+        // activate, a new mint switches over together with its token fees
+        if let Some(fee_mint) = fee_controller.pending_fee_mint.take() {
+            fee_controller.fee_mint = fee_mint;
+        }
+        if !fee_controller.pending_fees.is_empty() {
+            fee_controller.fees = fee_controller.pending_fees.clone();
+            fee_controller.pending_fees = Vec::new();
+        }
+        if !fee_controller.pending_token_fees.is_empty() {
+            fee_controller.token_fees = fee_controller.pending_token_fees.clone();
+            fee_controller.pending_token_fees = Vec::new();
+        }
+        if let Some(fee_increase_delay_seconds) = fee_controller.pending_fee_increase_delay_seconds.take() {
+            fee_controller.fee_increase_delay_seconds = fee_increase_delay_seconds;
+        }
+        if let Some(claim_fee_bps) = fee_controller.pending_claim_fee_bps.take() {
+            fee_controller.claim_fee_bps = claim_fee_bps;
+        }
+        fee_controller.pending_fees_effective_timestamp = 0;
+
+        Ok(())
+    }
+
+    pub fn update_fee_receivers(
+        ctx: Context<UpdateFeeReceivers>,
+        fee_receivers: Vec<FeeReceiver>,
+    ) -> ProgramResult {
+
+        // validate
+        let fee_controller = &ctx.accounts.fee_controller;
+        validate_authority_signers(
+            &fee_controller.fee_authorities,
+            fee_controller.fee_approval_threshold,
+            &ctx.accounts.signer,
+            ctx.remaining_accounts,
+        )?;
+        if fee_receivers.len() > MAX_NUM_FEE_RECEIVERS || fee_receivers.is_empty() {
+            return Err(ErrorCode::InvalidFeeReceivers.into());
+        }
+        let total_share_bps: u64 = fee_receivers.iter().map(|r| r.share_bps as u64).sum();
+        if total_share_bps != BPS_DENOMINATOR {
+            return Err(ErrorCode::InvalidFeeReceivers.into());
+        }
+
+        ctx.accounts.fee_controller.fee_receivers = fee_receivers;
+
+        Ok(())
+    }
+
+    pub fn reset_fee_stats(
+        ctx: Context<ResetFeeStats>,
+    ) -> ProgramResult {
+
+        // validate
+        let fee_controller = &ctx.accounts.fee_controller;
+        validate_authority_signers(
+            &fee_controller.fee_authorities,
+            fee_controller.fee_approval_threshold,
+            &ctx.accounts.signer,
+            ctx.remaining_accounts,
+        )?;
+
+        // log a snapshot of the closing period before zeroing the counters
+        let fee_controller = &mut ctx.accounts.fee_controller;
+        let current_timestamp = Clock::get().unwrap().unix_timestamp as u64;
+        msg!("Fee stats from {:?} to {:?}", fee_controller.fee_stats_reset_timestamp, current_timestamp);
+        msg!("fees_collected: {:?}", fee_controller.fees_collected);
+        msg!("token_fees_collected: {:?}", fee_controller.token_fees_collected);
+        msg!("fee_tx_count: {:?}", fee_controller.fee_tx_count);
+
+        fee_controller.fees_collected = vec![0; MAX_INSTRUCTIONS];
+        fee_controller.token_fees_collected = vec![0; MAX_INSTRUCTIONS];
+        fee_controller.fee_tx_count = vec![0; MAX_INSTRUCTIONS];
+        fee_controller.fee_stats_reset_timestamp = current_timestamp;
+
+        Ok(())
+    }
+
+    pub fn update_fee_exemptions(
+        ctx: Context<UpdateFeeExemptions>,
+        fee_exemptions: Vec<FeeExemption>,
+    ) -> ProgramResult {
+
+        // validate
+        let fee_controller = &ctx.accounts.fee_controller;
+        validate_authority_signers(
+            &fee_controller.fee_authorities,
+            fee_controller.fee_approval_threshold,
+            &ctx.accounts.signer,
+            ctx.remaining_accounts,
+        )?;
+        if fee_exemptions.len() > MAX_NUM_FEE_EXEMPTIONS {
+            return Err(ErrorCode::InvalidFeeExemptions.into());
+        }
+        if fee_exemptions.iter().any(|e| e.discount_bps as u64 > BPS_DENOMINATOR) {
+            return Err(ErrorCode::InvalidFeeExemptions.into());
+        }
+
+        ctx.accounts.fee_controller.fee_exemptions = fee_exemptions;
+
+        Ok(())
+    }
+
+    pub fn update_claim_fee(
+        ctx: Context<UpdateClaimFee>,
+        claim_fee_bps: u16,
+        protocol_fee_owner: Pubkey,
+    ) -> ProgramResult {
+
+        // validate
+        let fee_controller = &ctx.accounts.fee_controller;
+        validate_authority_signers(
+            &fee_controller.fee_authorities,
+            fee_controller.fee_approval_threshold,
+            &ctx.accounts.signer,
+            ctx.remaining_accounts,
+        )?;
+        if claim_fee_bps > MAX_CLAIM_FEE_BPS {
+            return Err(ErrorCode::MaxClaimFeeExceeded.into());
         }
+
+        // decreases apply now, increases wait out the delay
         let fee_controller = &mut ctx.accounts.fee_controller;
-        match instruction_name.as_str() {
-            "initialize_dao" => {fee_controller.initialize_dao = new_fee_amount_lamports;}
-            "initialize_stream" => {fee_controller.initialize_stream = new_fee_amount_lamports;}
-            "reactivate_stream" => {fee_controller.reactivate_stream = new_fee_amount_lamports;}
-            "propose_dao_command" => {fee_controller.propose_dao_command = new_fee_amount_lamports;}
-            "approve_dao_command" => {fee_controller.approve_dao_command = new_fee_amount_lamports;}
-            "execute_update_dao_multisig" => {fee_controller.execute_update_dao_multisig = new_fee_amount_lamports;}
-            "execute_deactivate_stream" => {fee_controller.execute_deactivate_stream = new_fee_amount_lamports;}
-            "execute_withdraw_from_stream" => {fee_controller.execute_withdraw_from_stream = new_fee_amount_lamports;}
-            "initialize_stake" => {fee_controller.initialize_stake = new_fee_amount_lamports;}
-            "stake_nft" => {fee_controller.stake_nft = new_fee_amount_lamports;}
-            "unstake_nft" => {fee_controller.unstake_nft = new_fee_amount_lamports;}
-            "initialize_connection" => {fee_controller.initialize_connection = new_fee_amount_lamports;}
-            "connect_to_stream" => {fee_controller.connect_to_stream = new_fee_amount_lamports;}
-            "claim_from_stream" => {fee_controller.claim_from_stream = new_fee_amount_lamports;}
-            "disconnect_from_stream" => {fee_controller.disconnect_from_stream = new_fee_amount_lamports;}
-            _ => return Err(ErrorCode::InvalidInstructionName.into())
-        }
-        msg!("Updated {:?} to {:?}", instruction_name, new_fee_amount_lamports);
+        if claim_fee_bps <= fee_controller.claim_fee_bps {
+            fee_controller.claim_fee_bps = claim_fee_bps;
+            fee_controller.pending_claim_fee_bps = None;
+        } else {
+            let current_timestamp = Clock::get().unwrap().unix_timestamp as u64;
+            fee_controller.pending_claim_fee_bps = Some(claim_fee_bps);
+            fee_controller.pending_fees_effective_timestamp = current_timestamp + fee_controller.fee_increase_delay_seconds;
+            msg!("Claim fee increase pending until {:?}", fee_controller.pending_fees_effective_timestamp);
+        }
+        fee_controller.protocol_fee_owner = protocol_fee_owner;
+
+        Ok(())
+    }
+
+    pub fn initialize_fee_vault(
+        ctx: Context<InitializeFeeVault>,
+    ) -> ProgramResult {
+
+        // validate
+        let fee_controller = &ctx.accounts.fee_controller;
+        validate_authority_signers(
+            &fee_controller.fee_authorities,
+            fee_controller.fee_approval_threshold,
+            &ctx.accounts.signer,
+            ctx.remaining_accounts,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn update_fee_mint(
+        ctx: Context<UpdateFeeMint>,
+        fee_mint: Option<Pubkey>,
+        token_fees: Vec<u64>,
+    ) -> ProgramResult {
+
+        // validate
+        let fee_controller = &ctx.accounts.fee_controller;
+        validate_authority_signers(
+            &fee_controller.fee_authorities,
+            fee_controller.fee_approval_threshold,
+            &ctx.accounts.signer,
+            ctx.remaining_accounts,
+        )?;
+        if token_fees.len() != NUM_FEE_INSTRUCTIONS {
+            return Err(ErrorCode::InvalidFeeSchedule.into());
+        }
+
+        // None falls back to charging lamports
+        let fee_controller: &mut FeeController = &mut ctx.accounts.fee_controller;
+        let mut token_fees = token_fees;
+        token_fees.resize(MAX_INSTRUCTIONS, 0);
+
+        // a new mint is staged with its token fees, the current schedule is charged until then.
+        // on the current mint decreases apply now and increases wait out the delay
+        let has_increase = if fee_controller.fee_mint != fee_mint {
+            fee_controller.pending_fee_mint = Some(fee_mint);
+            fee_controller.pending_token_fees = token_fees;
+            true
+        } else {
+            fee_controller.pending_fee_mint = None;
+            stage_fee_schedule(&mut fee_controller.token_fees, &mut fee_controller.pending_token_fees, token_fees)
+        };
+        if has_increase {
+            let current_timestamp = Clock::get().unwrap().unix_timestamp as u64;
+            fee_controller.pending_fees_effective_timestamp = current_timestamp + fee_controller.fee_increase_delay_seconds;
+            msg!("Token fee increases pending until {:?}", fee_controller.pending_fees_effective_timestamp);
+        }
+        if fee_controller.pending_fee_mint.is_some() {
+            msg!("Fee mint change to {:?} pending", fee_mint);
+        }
+
+        Ok(())
+    }
+
+    pub fn withdraw_from_fee_vault(
+        ctx: Context<WithdrawFromFeeVault>,
+        amount: u64,
+    ) -> ProgramResult {
+
+        // validate
+        let fee_controller = &ctx.accounts.fee_controller;
+        validate_authority_signers(
+            &fee_controller.fee_authorities,
+            fee_controller.fee_approval_threshold,
+            &ctx.accounts.signer,
+            ctx.remaining_accounts,
+        )?;
+
+        // get seeds to sign for fee controller
+        let (_fee_controller_pda, bump_seed) = Pubkey::find_program_address(&[FEE_CONTROLLER_PDA_SEED], ctx.program_id);
+        let seeds = &[FEE_CONTROLLER_PDA_SEED, &[bump_seed]];
+        let signer = &[&seeds[..]];
+
+        // transfer
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.fee_vault.to_account_info(),
+            to: ctx.accounts.receiver_token_account.to_account_info(),
+            authority: ctx.accounts.fee_controller.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, amount)?;
+
+        Ok(())
+    }
+
+    pub fn initialize_fee_override(
+        ctx: Context<InitializeFeeOverride>,
+    ) -> ProgramResult {
+
+        // validate
+        let fee_controller = &ctx.accounts.fee_controller;
+        validate_authority_signers(
+            &fee_controller.fee_authorities,
+            fee_controller.fee_approval_threshold,
+            &ctx.accounts.signer,
+            ctx.remaining_accounts,
+        )?;
+
+        // initialize override, charging the global fee until adjusted
+        let fee_override = &mut ctx.accounts.fee_override;
+        fee_override.dao_address = ctx.accounts.dao.key();
+        fee_override.fee_bps = vec![BPS_DENOMINATOR as u32; MAX_INSTRUCTIONS];
+        fee_override.pending_fee_bps = Vec::new();
+        fee_override.pending_fees_effective_timestamp = 0;
+
+        Ok(())
+    }
+
+    pub fn update_fee_override(
+        ctx: Context<UpdateFeeOverride>,
+        fee_instruction_int: u8,
+        fee_bps: u32,
+    ) -> ProgramResult {
+
+        // validate
+        let fee_controller = &ctx.accounts.fee_controller;
+        validate_authority_signers(
+            &fee_controller.fee_authorities,
+            fee_controller.fee_approval_threshold,
+            &ctx.accounts.signer,
+            ctx.remaining_accounts,
+        )?;
+        let fee_instruction: FeeInstruction = FromPrimitive::from_u8(fee_instruction_int).ok_or(ErrorCode::InvalidFeeInstruction)?;
+        if fee_bps > MAX_FEE_OVERRIDE_BPS {
+            return Err(ErrorCode::InvalidFeeOverride.into());
+        }
+
+        // 0 exempts, below BPS_DENOMINATOR discounts, above surcharges which wait out the fee increase delay
+        let current_timestamp = Clock::get().unwrap().unix_timestamp as u64;
+        let fee_increase_delay_seconds = ctx.accounts.fee_controller.fee_increase_delay_seconds;
+        let fee_override = &mut ctx.accounts.fee_override;
+        let has_increase = fee_override.set_fee_bps(fee_instruction, fee_bps, current_timestamp);
+        if has_increase {
+            fee_override.pending_fees_effective_timestamp = current_timestamp + fee_increase_delay_seconds;
+            msg!("Override increases pending until {:?}", fee_override.pending_fees_effective_timestamp);
+        }
+        msg!("Updated {:?} override to {:?} bps", fee_instruction, fee_bps);
+
+        Ok(())
+    }
+
+    // global config instructions
+    pub fn initialize_global_config(
+        ctx: Context<InitializeGlobalConfig>,
+    ) -> ProgramResult {
+
+        // validate
+        let fee_controller = &ctx.accounts.fee_controller;
+        validate_authority_signers(
+            &fee_controller.fee_authorities,
+            fee_controller.fee_approval_threshold,
+            &ctx.accounts.signer,
+            ctx.remaining_accounts,
+        )?;
+
+        // start at the hard ceilings
+        let global_config = &mut ctx.accounts.global_config;
+        global_config.max_decimals = MAX_DECIMALS;
+        global_config.max_stream_rate = MAX_STREAM_RATE;
+        global_config.max_connections_per_stream = MAX_CONNECTIONS_PER_STREAM;
+        global_config.is_paused = false;
+
+        Ok(())
+    }
+
+    pub fn update_global_config(
+        ctx: Context<UpdateGlobalConfig>,
+        max_decimals: u8,
+        max_stream_rate: u64,
+        max_connections_per_stream: u64,
+        is_paused: bool,
+    ) -> ProgramResult {
+
+        // validate
+        let fee_controller = &ctx.accounts.fee_controller;
+        validate_authority_signers(
+            &fee_controller.fee_authorities,
+            fee_controller.fee_approval_threshold,
+            &ctx.accounts.signer,
+            ctx.remaining_accounts,
+        )?;
+        if max_decimals > MAX_DECIMALS
+            || max_stream_rate > MAX_STREAM_RATE
+            || max_connections_per_stream > MAX_CONNECTIONS_PER_STREAM {
+            return Err(ErrorCode::InvalidGlobalConfig.into());
+        }
+
+        // existing streams and connections are unaffected by tighter limits
+        let global_config = &mut ctx.accounts.global_config;
+        global_config.max_decimals = max_decimals;
+        global_config.max_stream_rate = max_stream_rate;
+        global_config.max_connections_per_stream = max_connections_per_stream;
+        global_config.is_paused = is_paused;
+        msg!("Updated global config, paused: {:?}", is_paused);
+
         Ok(())
     }
 
     // dao instructions
-    pub fn initialize_dao(
-        ctx: Context<InitializeDao>,
-        councillors: Vec<Pubkey>,
-        approval_threshold: u64,
+    pub fn initialize_dao<'info>(
+        ctx: Context<'_, '_, '_, 'info, InitializeDao<'info>>,
+        name: String,
+        metadata: DaoMetadata,
+        governance_config: DaoGovernanceConfig,
     ) -> ProgramResult {
 
         // validate inputs
+        let DaoGovernanceConfig {
+            councillors,
+            councillor_roles,
+            approval_threshold,
+            proposal_voting_window_seconds,
+            execution_delay_seconds,
+            guardian,
+        } = governance_config;
         if councillors.len() > MAX_NUM_COUNCILLORS  || councillors.len() < 1 {
             return Err(ErrorCode::TooManyManagers.into());
         }
         if (approval_threshold as usize > councillors.len()) || (approval_threshold < 1) {
             return Err(ErrorCode::InvalidApprovalThreshold.into());
         }
+        validate_dao_name(&name)?;
+        // display name defaults to the fixed name
+        let mut metadata = metadata;
+        if metadata.display_name.is_empty() {
+            metadata.display_name = name.clone();
+        }
+        validate_dao_metadata(&metadata)?;
+        validate_governance_config(proposal_voting_window_seconds, execution_delay_seconds)?;
+        let role_permissions = default_role_permissions();
+        let role_approval_thresholds = vec![0; NUM_COUNCILLOR_ROLES];
+        validate_role_config(&councillors, &councillor_roles, approval_threshold, &role_permissions, &role_approval_thresholds)?;
 
-        // initialize dao
+        // initialize dao, the name is fixed since it seeds the address
         let dao = &mut ctx.accounts.dao;
+        dao.creator = ctx.accounts.signer.key();
+        dao.name = name;
+        dao.metadata = metadata;
         dao.councillors = councillors;
         dao.approval_threshold = approval_threshold;
+        dao.councillor_roles = councillor_roles;
+        dao.role_permissions = role_permissions;
+        dao.role_approval_thresholds = role_approval_thresholds;
         dao.num_streams = 0;
 
+        // init emergency pause, default pubkey leaves pausing to councillors only
+        dao.guardian = guardian;
+        dao.paused = false;
+
         // init multisig variables
-        let mut signers = Vec::new();
-        signers.resize(dao.councillors.len(), false);
-        dao.proposal_signers = signers;
-        dao.proposal_is_active = false;
+        dao.multisig_nonce = 0;
+        dao.num_proposals = 0;
+        dao.proposal_voting_window_seconds = proposal_voting_window_seconds;
+        dao.execution_delay_seconds = execution_delay_seconds;
 
         // tx fee
         let signer_handle = &ctx.accounts.signer;
-        let fee_rx_handle = &ctx.accounts.fee_receiver_address;
-        let fee_lamports = ctx.accounts.fee_controller.initialize_dao;
+        let fee_rx_handles = ctx.remaining_accounts;
+        let fee_dao_address = None;
 
-        transfer_fee(signer_handle, fee_rx_handle, fee_lamports)?;
+        transfer_fee(signer_handle, &mut ctx.accounts.fee_controller, fee_rx_handles, &ctx.accounts.token_program, FeeInstruction::InitializeDao, fee_dao_address, 0)?;
 
         Ok(())
     }
@@ -142,30 +669,32 @@ pub mod gigadao_staking {
         is_simulation: bool,
     ) -> ProgramResult {
 
+        // blocked while the protocol or dao is paused
+        validate_protocol_not_paused(&ctx.accounts.global_config)?;
+        validate_dao_not_paused(&ctx.accounts.dao)?;
+
         // validate inputs
         if verified_creator_addresses.len() > MAX_NUM_VERIFIED_CREATOR_ADDRESSES {
             return Err(ErrorCode::TooManyVerifiedCreatorAddresses.into());
         }
 
-        // check that signer is a dao councillor
-        let _owner_index = ctx.accounts.dao.councillors
-            .iter()
-            .position(|a| a == ctx.accounts.signer.key)
-            .ok_or(ErrorCode::InvalidCouncillor)?;
+        // check that signer is a dao councillor allowed to create streams
+        let _owner_index = ctx.accounts.dao.councillor_index_with_permission(ctx.accounts.signer.key, PERMISSION_INITIALIZE_STREAM)?;
 
         // check decimals does not exceed max supported
-        if ctx.accounts.token_mint.decimals > MAX_DECIMALS {
+        if ctx.accounts.token_mint.decimals > ctx.accounts.global_config.max_decimals {
             return Err(ErrorCode::MaxSupportedDecimalsExceeded.into());
         }
 
         // check stream rate against maximum supported
-        if stream_rate > MAX_STREAM_RATE {
+        if stream_rate > ctx.accounts.global_config.max_stream_rate {
             return Err(ErrorCode::MaxSupportedStreamRateExceeded.into());
         }
 
         // initialize stream
         let stream = &mut ctx.accounts.stream;
         stream.dao_address = ctx.accounts.dao.key();
+        stream.stream_index = ctx.accounts.dao.num_streams;
         stream.token_mint_address = ctx.accounts.token_mint.key();
         stream.token_pool_address = ctx.accounts.token_pool.key();
         stream.verified_creator_addresses = verified_creator_addresses;
@@ -176,28 +705,31 @@ pub mod gigadao_staking {
         stream.num_connections = 0;
         stream.total_streamed = 0;
         stream.total_claimed = 0;
+        stream.earned_per_connection = 0;
         stream.last_update_timestamp = Clock::get().unwrap().unix_timestamp as u64;
 
+        // register stream under the next index
+        ctx.accounts.dao.num_streams += 1;
+
         // tx fee
         // let signer_handle = &ctx.accounts.signer;
-        // let fee_rx_handle = &ctx.accounts.fee_receiver_address;
-        // let fee_lamports = ctx.accounts.fee_controller.initialize_stream;
+        // let fee_rx_handles = ctx.remaining_accounts;
+        // let fee_dao_address = Some(ctx.accounts.dao.key());
         //
-        // transfer_fee(signer_handle, fee_rx_handle, fee_lamports)?;
+        // transfer_fee(signer_handle, &mut ctx.accounts.fee_controller, fee_rx_handles, &ctx.accounts.token_program, FeeInstruction::InitializeStream, fee_dao_address, 0)?;
 
 
         Ok(())
     }
 
-    pub fn reactivate_stream(
-        ctx: Context<ReactivateStream>
+    pub fn reactivate_stream<'info>(
+        ctx: Context<'_, '_, '_, 'info, ReactivateStream<'info>>
     ) -> ProgramResult {
 
-        // check that signer is a dao councillor
-        let _owner_index = ctx.accounts.dao.councillors
-            .iter()
-            .position(|a| a == ctx.accounts.signer.key)
-            .ok_or(ErrorCode::InvalidCouncillor)?;
+        // check that signer is a dao councillor allowed to reactivate streams
+        validate_protocol_not_paused(&ctx.accounts.global_config)?;
+        validate_dao_not_paused(&ctx.accounts.dao)?;
+        let _owner_index = ctx.accounts.dao.councillor_index_with_permission(ctx.accounts.signer.key, PERMISSION_REACTIVATE_STREAM)?;
 
         // calculate recent streamed
         let stream = &mut ctx.accounts.stream;
@@ -217,502 +749,537 @@ pub mod gigadao_staking {
         }
 
         stream.total_streamed += recently_streamed;
+        stream.earned_per_connection += stream.stream_rate * seconds_since_last_update;
         stream.last_update_timestamp = current_timestamp;
         stream.is_active = true;
 
         // tx fee
         let signer_handle = &ctx.accounts.signer;
-        let fee_rx_handle = &ctx.accounts.fee_receiver_address;
-        let fee_lamports = ctx.accounts.fee_controller.reactivate_stream;
+        let fee_rx_handles = ctx.remaining_accounts;
+        let fee_dao_address = Some(ctx.accounts.dao.key());
 
-        transfer_fee(signer_handle, fee_rx_handle, fee_lamports)?;
+        transfer_fee(signer_handle, &mut ctx.accounts.fee_controller, fee_rx_handles, &ctx.accounts.token_program, FeeInstruction::ReactivateStream, fee_dao_address, 0)?;
 
 
         Ok(())
     }
 
-    pub fn propose_dao_command(
-        ctx: Context<ProposeDaoCommand>,
+    pub fn propose_dao_command<'info>(
+        ctx: Context<'_, '_, '_, 'info, ProposeDaoCommand<'info>>,
         proposal_type_int: u8,
-        proposed_councillors: Vec<Pubkey>,
-        proposed_approval_threshold: u64,
-        proposed_deactivation_stream: Pubkey,
-        proposed_withdraw_amount: u64,
-        proposed_withdrawal_receiver_owner: Pubkey,
-        proposed_withdrawal_stream: Pubkey,
+        params: ProposalParams,
     ) -> ProgramResult {
 
         // convert proposal type
         let proposal_type: ProposalType = FromPrimitive::from_u8(proposal_type_int).ok_or(ErrorCode::InvalidProposalType)?;
 
-        // check that signer is a dao councillor
+        // check that signer is a dao councillor allowed to propose and vote on this type
         let dao = &ctx.accounts.dao;
-        let councillor_index = dao.councillors
-            .iter()
-            .position(|a| a == ctx.accounts.signer.key)
-            .ok_or(ErrorCode::InvalidCouncillor)?;
+        let councillor_index = dao.councillor_index_with_permission(ctx.accounts.signer.key, PERMISSION_PROPOSE | proposal_type.vote_permission())?;
 
-        let dao = &mut ctx.accounts.dao;
+        // validate input
         match proposal_type {
             ProposalType::UpdateMultisig => {
-                // validate input
-                if proposed_councillors.len() > MAX_NUM_COUNCILLORS || proposed_councillors.len() < 1 {
-                    return Err(ErrorCode::TooManyManagers.into());
-                }
-                if (proposed_approval_threshold as usize > proposed_councillors.len()) || (proposed_approval_threshold < 1) {
-                    return Err(ErrorCode::InvalidApprovalThreshold.into());
+                validate_authority_config(&params.proposed_councillors, params.proposed_approval_threshold)?;
+                validate_role_config(
+                    &params.proposed_councillors,
+                    &params.proposed_councillor_roles,
+                    params.proposed_approval_threshold,
+                    &dao.role_permissions,
+                    &dao.role_approval_thresholds,
+                )?;
+            },
+            ProposalType::UpdateRoles => {
+                validate_role_config(
+                    &dao.councillors,
+                    &dao.councillor_roles,
+                    dao.approval_threshold,
+                    &params.proposed_role_permissions,
+                    &params.proposed_role_approval_thresholds,
+                )?;
+            },
+            ProposalType::DeactivateStream => (),
+            ProposalType::WithdrawFromStream => (),
+            ProposalType::UpdateDaoConfig => {
+                validate_governance_config(params.proposed_voting_window_seconds, params.proposed_execution_delay_seconds)?;
+            },
+            ProposalType::UpdateDaoMetadata => {
+                validate_dao_metadata(&params.proposed_metadata)?;
+            },
+            ProposalType::UpdateStreamRate => {
+                if params.proposed_stream_rate > MAX_STREAM_RATE {
+                    return Err(ErrorCode::MaxSupportedStreamRateExceeded.into());
                 }
-                dao.proposed_councillors = proposed_councillors;
-                dao.proposed_approval_threshold = proposed_approval_threshold;
             },
-            ProposalType::DeactivateStream => {
-                dao.proposed_deactivation_stream = proposed_deactivation_stream;
+            ProposalType::AddVerifiedCreator => (),
+            ProposalType::RemoveVerifiedCreator => (),
+            ProposalType::CloseStream => (),
+            ProposalType::UnpauseDao => (),
+            ProposalType::WithdrawFromTreasury => (),
+            ProposalType::FundStreamFromTreasury => (),
+            ProposalType::CustomInstruction => {
+                if params.proposed_instruction_accounts.len() > MAX_CUSTOM_INSTRUCTION_ACCOUNTS {
+                    return Err(ErrorCode::InvalidCustomInstruction.into());
+                }
+                if params.proposed_instruction_data.len() > MAX_CUSTOM_INSTRUCTION_DATA_LEN {
+                    return Err(ErrorCode::InvalidCustomInstruction.into());
+                }
+                if params.proposed_instruction_program_id == *ctx.program_id {
+                    return Err(ErrorCode::InvalidCustomInstruction.into());
+                }
             },
-            ProposalType::WithdrawFromStream => {
-                dao.proposed_withdrawal_amount = proposed_withdraw_amount;
-                dao.proposed_withdrawal_receiver_owner = proposed_withdrawal_receiver_owner;
-                dao.proposed_withdrawal_stream = proposed_withdrawal_stream
-            }
         }
 
-        // reset signers
-        for i in 0..dao.councillors.len(){
-            if i == councillor_index {
-                dao.proposal_signers[i] = true;
-            } else {
-                dao.proposal_signers[i] = false;
-            }
-        }
+        // initialize proposal, proposer approves by default
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.dao_address = dao.key();
+        proposal.proposal_index = dao.num_proposals;
+        proposal.proposer = ctx.accounts.signer.key();
+        proposal.content_hash = proposal_content_hash(proposal_type, &params)?;
+        proposal.proposal_type = proposal_type;
+        proposal.params = params;
+        proposal.multisig_nonce = dao.multisig_nonce;
+
+        // voting deadline is fixed at creation, zero window never expires
+        let current_timestamp = Clock::get().unwrap().unix_timestamp as u64;
+        proposal.created_timestamp = current_timestamp;
+        proposal.voting_deadline_timestamp = if dao.proposal_voting_window_seconds > 0 {
+            current_timestamp + dao.proposal_voting_window_seconds
+        } else {
+            0
+        };
+
+        let mut signers = Vec::new();
+        signers.resize(dao.councillors.len(), false);
+        signers[councillor_index] = true;
+        proposal.proposal_signers = signers;
+        proposal.status = ProposalStatus::Active;
+        proposal.threshold_reached_timestamp = 0;
+        proposal.update_threshold_reached(dao, current_timestamp);
 
         // finalize
-        dao.proposal_type = proposal_type;
-        dao.proposal_is_active = true;
+        ctx.accounts.dao.num_proposals += 1;
 
         // tx fee
         let signer_handle = &ctx.accounts.signer;
-        let fee_rx_handle = &ctx.accounts.fee_receiver_address;
-        let fee_lamports = ctx.accounts.fee_controller.propose_dao_command;
+        let fee_rx_handles = ctx.remaining_accounts;
+        let fee_dao_address = Some(ctx.accounts.dao.key());
 
-        transfer_fee(signer_handle, fee_rx_handle, fee_lamports)?;
+        transfer_fee(signer_handle, &mut ctx.accounts.fee_controller, fee_rx_handles, &ctx.accounts.token_program, FeeInstruction::ProposeDaoCommand, fee_dao_address, 0)?;
 
 
         Ok(())
     }
 
-    pub fn approve_dao_command(
-        ctx: Context<ApproveDaoCommand>,
+    pub fn approve_dao_command<'info>(
+        ctx: Context<'_, '_, '_, 'info, ApproveDaoCommand<'info>>,
+        expected_content_hash: [u8; 32],
     ) -> ProgramResult {
         let dao = &ctx.accounts.dao;
-        if !dao.proposal_is_active {
-            return Err(ErrorCode::ProposalNotActive.into());
+        let proposal = &ctx.accounts.proposal;
+        validate_proposal_is_current(dao, proposal)?;
+
+        // only approve the exact content the councillor reviewed
+        if proposal.content_hash != expected_content_hash {
+            return Err(ErrorCode::ProposalHashMismatch.into());
         }
+        let councillor_index = dao.councillor_index_with_permission(ctx.accounts.signer.key, proposal.proposal_type.vote_permission())?;
+        let current_timestamp = Clock::get().unwrap().unix_timestamp as u64;
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.proposal_signers[councillor_index] = true;
+        proposal.update_threshold_reached(dao, current_timestamp);
+
+
+        // tx fee
+        let signer_handle = &ctx.accounts.signer;
+        let fee_rx_handles = ctx.remaining_accounts;
+        let fee_dao_address = Some(ctx.accounts.dao.key());
+
+        transfer_fee(signer_handle, &mut ctx.accounts.fee_controller, fee_rx_handles, &ctx.accounts.token_program, FeeInstruction::ApproveDaoCommand, fee_dao_address, 0)?;
+
+
+
+        Ok(())
+    }
+
+    pub fn revoke_approval<'info>(
+        ctx: Context<'_, '_, '_, 'info, RevokeApproval<'info>>,
+    ) -> ProgramResult {
+        let dao = &ctx.accounts.dao;
+        let proposal = &ctx.accounts.proposal;
+        validate_proposal_is_current(dao, proposal)?;
         let councillor_index = dao.councillors
             .iter()
             .position(|a| a == ctx.accounts.signer.key)
             .ok_or(ErrorCode::InvalidCouncillor)?;
-        ctx.accounts.dao.proposal_signers[councillor_index] = true;
+        if !proposal.proposal_signers[councillor_index] {
+            return Err(ErrorCode::ApprovalNotFound.into());
+        }
+
+        // dropping below threshold restarts the execution timelock
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.proposal_signers[councillor_index] = false;
+        if !dao.approval_threshold_met(proposal) {
+            proposal.threshold_reached_timestamp = 0;
+        }
 
+        emit!(ApprovalRevoked {
+            dao_address: ctx.accounts.dao.key(),
+            proposal_address: proposal.key(),
+            councillor: ctx.accounts.signer.key(),
+            num_approvals: proposal.num_approvals(),
+        });
 
         // tx fee
         let signer_handle = &ctx.accounts.signer;
-        let fee_rx_handle = &ctx.accounts.fee_receiver_address;
-        let fee_lamports = ctx.accounts.fee_controller.approve_dao_command;
-
-        transfer_fee(signer_handle, fee_rx_handle, fee_lamports)?;
+        let fee_rx_handles = ctx.remaining_accounts;
+        let fee_dao_address = Some(ctx.accounts.dao.key());
 
+        transfer_fee(signer_handle, &mut ctx.accounts.fee_controller, fee_rx_handles, &ctx.accounts.token_program, FeeInstruction::RevokeApproval, fee_dao_address, 0)?;
 
 
         Ok(())
     }
 
-    // dao commands
-    pub fn execute_update_dao_multisig(
-        ctx: Context<ExecuteUpdateDaoMultisig>,
+    pub fn cancel_proposal<'info>(
+        ctx: Context<'_, '_, '_, 'info, CancelProposal<'info>>,
     ) -> ProgramResult {
+        let dao = &ctx.accounts.dao;
+        let proposal = &ctx.accounts.proposal;
+        if proposal.status != ProposalStatus::Active {
+            return Err(ErrorCode::ProposalNotActive.into());
+        }
+        let _councillor_index = dao.councillors
+            .iter()
+            .position(|a| a == ctx.accounts.signer.key)
+            .ok_or(ErrorCode::InvalidCouncillor)?;
 
-        // validate
-        let dao = &mut ctx.accounts.dao;
-        validate_proposal_approval(dao, ctx.accounts.signer.key)?;
-        match dao.proposal_type {
-            ProposalType::UpdateMultisig => (),
-            _ => return Err(ErrorCode::MismatchProposalType.into())
+        // proposer may cancel alone, anyone else needs threshold councillors co-signing
+        if proposal.proposer != ctx.accounts.signer.key() {
+            validate_authority_signers(
+                &dao.councillors,
+                dao.approval_threshold,
+                &ctx.accounts.signer,
+                ctx.remaining_accounts,
+            )?;
         }
+        ctx.accounts.proposal.status = ProposalStatus::Cancelled;
 
-        // update multisig
-        dao.councillors = dao.proposed_councillors.clone();
-        dao.approval_threshold = dao.proposed_approval_threshold;
-        let mut signers = Vec::new();
-        signers.resize(dao.councillors.len(), false);
-        dao.proposal_signers = signers;
-        dao.proposal_is_active = false;
+        emit!(ProposalCancelled {
+            dao_address: ctx.accounts.dao.key(),
+            proposal_address: ctx.accounts.proposal.key(),
+            cancelled_by: ctx.accounts.signer.key(),
+        });
 
         // tx fee
         let signer_handle = &ctx.accounts.signer;
-        let fee_rx_handle = &ctx.accounts.fee_receiver_address;
-        let fee_lamports = ctx.accounts.fee_controller.execute_update_dao_multisig;
+        let fee_rx_handles = ctx.remaining_accounts;
+        let fee_dao_address = Some(ctx.accounts.dao.key());
 
-        transfer_fee(signer_handle, fee_rx_handle, fee_lamports)?;
+        transfer_fee(signer_handle, &mut ctx.accounts.fee_controller, fee_rx_handles, &ctx.accounts.token_program, FeeInstruction::CancelProposal, fee_dao_address, 0)?;
 
 
         Ok(())
     }
 
-    pub fn execute_deactivate_stream(
-        ctx: Context<ExecuteDeactivateStream>,
+    pub fn expire_proposal(
+        ctx: Context<ExpireProposal>,
     ) -> ProgramResult {
 
-        // validate
-        let dao = &mut ctx.accounts.dao;
-        validate_proposal_approval(dao, ctx.accounts.signer.key)?;
-        match dao.proposal_type {
-            ProposalType::DeactivateStream => (),
-            _ => return Err(ErrorCode::MismatchProposalType.into())
+        // permissionless, only checks that voting or the execution window has closed
+        let dao = &ctx.accounts.dao;
+        let proposal = &mut ctx.accounts.proposal;
+        if proposal.status != ProposalStatus::Active {
+            return Err(ErrorCode::ProposalNotActive.into());
         }
-
-        // update stream
-        let stream = &mut ctx.accounts.stream;
-        let current_pool_balance = ctx.accounts.token_pool.amount;
         let current_timestamp = Clock::get().unwrap().unix_timestamp as u64;
-        let add_connection = false;
-        update_stream_state(stream, current_pool_balance, current_timestamp, add_connection)?;
-
-        // deactivate and finalize
-        stream.is_active = false;
-        dao.proposal_is_active = false;
-
-        // tx fee
-        let signer_handle = &ctx.accounts.signer;
-        let fee_rx_handle = &ctx.accounts.fee_receiver_address;
-        let fee_lamports = ctx.accounts.fee_controller.execute_deactivate_stream;
-
-        transfer_fee(signer_handle, fee_rx_handle, fee_lamports)?;
+        if !proposal.is_expired(current_timestamp) {
+            return Err(ErrorCode::ProposalNotExpired.into());
+        }
 
+        // approved in time, executable until its execution window closes
+        if proposal.threshold_reached_timestamp > 0 && !proposal.is_execution_expired(dao, current_timestamp) {
+            return Err(ErrorCode::ProposalNotExpired.into());
+        }
+        proposal.status = ProposalStatus::Expired;
 
         Ok(())
     }
 
-    pub fn execute_withdraw_from_stream(
-        ctx: Context<ExecuteWithdrawFromStream>,
+    // dao commands
+    pub fn execute_update_dao_multisig<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteUpdateDaoMultisig<'info>>,
     ) -> ProgramResult {
 
         // validate
         let dao = &mut ctx.accounts.dao;
-        validate_proposal_approval(dao, ctx.accounts.signer.key)?;
-        match dao.proposal_type {
-            ProposalType::WithdrawFromStream => (),
+        let proposal = &mut ctx.accounts.proposal;
+        validate_proposal_approval(dao, proposal, ctx.accounts.signer.key)?;
+        match proposal.proposal_type {
+            ProposalType::UpdateMultisig => (),
             _ => return Err(ErrorCode::MismatchProposalType.into())
         }
 
-        // update stream
-        let stream = &mut ctx.accounts.stream;
-        let current_pool_balance = ctx.accounts.token_pool.amount;
-        let current_timestamp = Clock::get().unwrap().unix_timestamp as u64;
-        let add_connection = false;
-        update_stream_state(stream, current_pool_balance, current_timestamp, add_connection)?;
+        // update multisig, bumping the nonce invalidates approvals on other open proposals
+        validate_role_config(
+            &proposal.params.proposed_councillors,
+            &proposal.params.proposed_councillor_roles,
+            proposal.params.proposed_approval_threshold,
+            &dao.role_permissions,
+            &dao.role_approval_thresholds,
+        )?;
+        dao.councillors = proposal.params.proposed_councillors.clone();
+        dao.councillor_roles = proposal.params.proposed_councillor_roles.clone();
+        dao.approval_threshold = proposal.params.proposed_approval_threshold;
+        dao.multisig_nonce += 1;
+        proposal.status = ProposalStatus::Executed;
 
-        // check proposed receiver match
-        if ctx.accounts.receiver_token_account.owner != dao.proposed_withdrawal_receiver_owner {
-            return Err(ErrorCode::InvalidProposedReceiverOwner.into());
-        }
+        // tx fee
+        let signer_handle = &ctx.accounts.signer;
+        let fee_rx_handles = ctx.remaining_accounts;
+        let fee_dao_address = Some(ctx.accounts.dao.key());
 
-        // check proposed withdraw stream
-        if dao.proposed_withdrawal_stream != stream.key() {
-            return Err(ErrorCode::StreamMismatch.into());
-        }
+        transfer_fee(signer_handle, &mut ctx.accounts.fee_controller, fee_rx_handles, &ctx.accounts.token_program, FeeInstruction::ExecuteUpdateDaoMultisig, fee_dao_address, 0)?;
 
-        // check proposed amount <= available amount
-        let unclaimed_amount = stream.total_streamed - stream.total_claimed;
-        let available_amount = current_pool_balance - unclaimed_amount;
-        let proposed_withdrawal_amount = dao.proposed_withdrawal_amount;
-        if proposed_withdrawal_amount > available_amount {
-            return Err(ErrorCode::InsufficientFunds.into());
-        }
 
-        // get seeds to sign for auth_pda
-        let dao_address = dao.key();
-        let (dao_auth_pda, bump_seed) = Pubkey::find_program_address(&[dao_address.as_ref(), DAO_AUTH_PDA_SEED], ctx.program_id);
-        let seeds = &[dao_address.as_ref(), &DAO_AUTH_PDA_SEED[..], &[bump_seed]];
-        let signer = &[&seeds[..]];
+        Ok(())
+    }
 
-        // check pda addy correct
-        if dao_auth_pda != ctx.accounts.dao_auth_pda.key() {
-            return Err(ErrorCode::InvalidAuthPda.into());
-        }
+    pub fn pause_dao<'info>(
+        ctx: Context<'_, '_, '_, 'info, PauseDao<'info>>,
+    ) -> ProgramResult {
 
-        // transfer
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.token_pool.to_account_info(),
-            to: ctx.accounts.receiver_token_account.to_account_info(),
-            authority: ctx.accounts.dao_auth_pda.to_account_info(),
-        };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-        token::transfer(cpi_ctx, proposed_withdrawal_amount)?;
+        // guardian or any single councillor can pause
+        let dao = &mut ctx.accounts.dao;
+        let signer_key = ctx.accounts.signer.key();
+        let is_guardian = dao.guardian != Pubkey::default() && dao.guardian == signer_key;
+        if !is_guardian && !dao.councillors.contains(&signer_key) {
+            return Err(ErrorCode::InvalidCouncillor.into());
+        }
+        dao.paused = true;
 
-        // finalize
-        dao.proposal_is_active = false;
+        emit!(DaoPaused {
+            dao_address: dao.key(),
+            paused_by: signer_key,
+        });
 
         // tx fee
         let signer_handle = &ctx.accounts.signer;
-        let fee_rx_handle = &ctx.accounts.fee_receiver_address;
-        let fee_lamports = ctx.accounts.fee_controller.execute_withdraw_from_stream;
+        let fee_rx_handles = ctx.remaining_accounts;
+        let fee_dao_address = Some(ctx.accounts.dao.key());
 
-        transfer_fee(signer_handle, fee_rx_handle, fee_lamports)?;
+        transfer_fee(signer_handle, &mut ctx.accounts.fee_controller, fee_rx_handles, &ctx.accounts.token_program, FeeInstruction::PauseDao, fee_dao_address, 0)?;
 
 
         Ok(())
     }
 
-    // Staker instructions
-    pub fn initialize_stake(
-        ctx: Context<InitializeStake>,
+    pub fn execute_unpause_dao<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteUnpauseDao<'info>>,
     ) -> ProgramResult {
 
-        // initialize stake
-        let stake = &mut ctx.accounts.stake;
-        stake.owner_address = ctx.accounts.signer.key();
-        stake.nft_mint_address = ctx.accounts.nft_mint.key();
-        stake.nft_vault_address = ctx.accounts.nft_vault.key();
+        // validate
+        let dao = &mut ctx.accounts.dao;
+        let proposal = &mut ctx.accounts.proposal;
+        validate_proposal_approval(dao, proposal, ctx.accounts.signer.key)?;
+        match proposal.proposal_type {
+            ProposalType::UnpauseDao => (),
+            _ => return Err(ErrorCode::MismatchProposalType.into())
+        }
 
-        stake.is_active = false;
-        stake.num_connections = 0;
-        stake.cumulative_seconds_staked = 0;
-        stake.last_stake_timestamp = 0;
+        // unpause and finalize
+        dao.paused = false;
+        proposal.status = ProposalStatus::Executed;
 
         // tx fee
         let signer_handle = &ctx.accounts.signer;
-        let fee_rx_handle = &ctx.accounts.fee_receiver_address;
-        let fee_lamports = ctx.accounts.fee_controller.initialize_stake;
+        let fee_rx_handles = ctx.remaining_accounts;
+        let fee_dao_address = Some(ctx.accounts.dao.key());
 
-        transfer_fee(signer_handle, fee_rx_handle, fee_lamports)?;
+        transfer_fee(signer_handle, &mut ctx.accounts.fee_controller, fee_rx_handles, &ctx.accounts.token_program, FeeInstruction::ExecuteUnpauseDao, fee_dao_address, 0)?;
 
 
         Ok(())
     }
 
-    pub fn stake_nft(
-        ctx: Context<StakeNft>,
+    pub fn execute_update_dao_roles<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteUpdateDaoRoles<'info>>,
     ) -> ProgramResult {
 
-        // transfer nft between token accounts
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.sender_nft_account.to_account_info(),
-            to: ctx.accounts.nft_vault.to_account_info(),
-            authority: ctx.accounts.signer.to_account_info(),
-        };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-        token::transfer(cpi_ctx, 1)?;
+        // validate
+        let dao = &mut ctx.accounts.dao;
+        let proposal = &mut ctx.accounts.proposal;
+        validate_proposal_approval(dao, proposal, ctx.accounts.signer.key)?;
+        match proposal.proposal_type {
+            ProposalType::UpdateRoles => (),
+            _ => return Err(ErrorCode::MismatchProposalType.into())
+        }
+        validate_role_config(
+            &dao.councillors,
+            &dao.councillor_roles,
+            dao.approval_threshold,
+            &proposal.params.proposed_role_permissions,
+            &proposal.params.proposed_role_approval_thresholds,
+        )?;
 
-        // update stake state
-        ctx.accounts.stake.is_active = true;
-        ctx.accounts.stake.last_stake_timestamp = Clock::get().unwrap().unix_timestamp as u64;
+        // update roles, bumping the nonce invalidates approvals cast under the old permissions
+        dao.role_permissions = proposal.params.proposed_role_permissions.clone();
+        dao.role_approval_thresholds = proposal.params.proposed_role_approval_thresholds.clone();
+        dao.multisig_nonce += 1;
+        proposal.status = ProposalStatus::Executed;
 
         // tx fee
         let signer_handle = &ctx.accounts.signer;
-        let fee_rx_handle = &ctx.accounts.fee_receiver_address;
-        let fee_lamports = ctx.accounts.fee_controller.stake_nft;
+        let fee_rx_handles = ctx.remaining_accounts;
+        let fee_dao_address = Some(ctx.accounts.dao.key());
 
-        transfer_fee(signer_handle, fee_rx_handle, fee_lamports)?;
+        transfer_fee(signer_handle, &mut ctx.accounts.fee_controller, fee_rx_handles, &ctx.accounts.token_program, FeeInstruction::ExecuteUpdateDaoRoles, fee_dao_address, 0)?;
 
 
         Ok(())
     }
 
-    pub fn unstake_nft(
-        ctx: Context<UnstakeNft>,
+    pub fn execute_update_dao_metadata<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteUpdateDaoMetadata<'info>>,
     ) -> ProgramResult {
 
-        // checks stake.num_connections == 0 in account constraints
-
-        // init vault signer
-        let stake_address = ctx.accounts.stake.key();
-        let (stake_auth_pda, bump_seed) = Pubkey::find_program_address(&[stake_address.as_ref(), STAKE_AUTH_PDA_SEED], ctx.program_id);
-        let seeds = &[stake_address.as_ref(), &STAKE_AUTH_PDA_SEED[..], &[bump_seed]];
-        let signer = &[&seeds[..]];
-
-        // check pda addy correct
-        if stake_auth_pda != ctx.accounts.stake_auth_pda.key() {
-            return Err(ErrorCode::InvalidAuthPda.into());
+        // validate
+        let dao = &mut ctx.accounts.dao;
+        let proposal = &mut ctx.accounts.proposal;
+        validate_proposal_approval(dao, proposal, ctx.accounts.signer.key)?;
+        match proposal.proposal_type {
+            ProposalType::UpdateDaoMetadata => (),
+            _ => return Err(ErrorCode::MismatchProposalType.into())
         }
 
-        // transfer
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.nft_vault.to_account_info(),
-            to: ctx.accounts.receiver_nft_account.to_account_info(),
-            authority: ctx.accounts.stake_auth_pda.to_account_info(),
-        };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-        token::transfer(cpi_ctx, 1)?;
-
-        // update stake state
-        ctx.accounts.stake.is_active = false;
+        // update metadata including the display name, name stays as it seeds the dao address
+        dao.metadata = proposal.params.proposed_metadata.clone();
+        proposal.status = ProposalStatus::Executed;
 
         // tx fee
         let signer_handle = &ctx.accounts.signer;
-        let fee_rx_handle = &ctx.accounts.fee_receiver_address;
-        let fee_lamports = ctx.accounts.fee_controller.unstake_nft;
+        let fee_rx_handles = ctx.remaining_accounts;
+        let fee_dao_address = Some(ctx.accounts.dao.key());
 
-        transfer_fee(signer_handle, fee_rx_handle, fee_lamports)?;
+        transfer_fee(signer_handle, &mut ctx.accounts.fee_controller, fee_rx_handles, &ctx.accounts.token_program, FeeInstruction::ExecuteUpdateDaoMetadata, fee_dao_address, 0)?;
 
 
         Ok(())
     }
 
-    pub fn initialize_connection(
-        ctx: Context<InitializeConnection>,
+    pub fn execute_update_dao_config<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteUpdateDaoConfig<'info>>,
     ) -> ProgramResult {
-        ctx.accounts.connection.is_active = false;
+
+        // validate
+        let dao = &mut ctx.accounts.dao;
+        let proposal = &mut ctx.accounts.proposal;
+        validate_proposal_approval(dao, proposal, ctx.accounts.signer.key)?;
+        match proposal.proposal_type {
+            ProposalType::UpdateDaoConfig => (),
+            _ => return Err(ErrorCode::MismatchProposalType.into())
+        }
+
+        // update config, open proposals keep the deadline they were created with
+        dao.proposal_voting_window_seconds = proposal.params.proposed_voting_window_seconds;
+        dao.execution_delay_seconds = proposal.params.proposed_execution_delay_seconds;
+        dao.guardian = proposal.params.proposed_guardian;
+        proposal.status = ProposalStatus::Executed;
 
         // tx fee
         let signer_handle = &ctx.accounts.signer;
-        let fee_rx_handle = &ctx.accounts.fee_receiver_address;
-        let fee_lamports = ctx.accounts.fee_controller.initialize_connection;
+        let fee_rx_handles = ctx.remaining_accounts;
+        let fee_dao_address = Some(ctx.accounts.dao.key());
 
-        transfer_fee(signer_handle, fee_rx_handle, fee_lamports)?;
+        transfer_fee(signer_handle, &mut ctx.accounts.fee_controller, fee_rx_handles, &ctx.accounts.token_program, FeeInstruction::ExecuteUpdateDaoConfig, fee_dao_address, 0)?;
 
 
         Ok(())
     }
 
-    pub fn connect_to_stream(
-        ctx: Context<ConnectToStream>,
+    pub fn execute_deactivate_stream<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteDeactivateStream<'info>>,
     ) -> ProgramResult {
 
-        // manually lookup metaplex metadata pda and ensure match with address
-        let metadata_program_id: Pubkey = if ctx.accounts.stream.is_simulation {
-            ID
-        } else {
-            spl_token_metadata::ID
-        };
-        let nft_mint = &ctx.accounts.stake.nft_mint_address;
-        let metadata_seeds = &[
-            METADATA_PREFIX,
-            metadata_program_id.as_ref(),
-            nft_mint.as_ref(),
-        ];
-        let (metaplex_metadata_pda, _bump) = Pubkey::find_program_address(metadata_seeds, &metadata_program_id);
-        if metaplex_metadata_pda != ctx.accounts.metaplex_metadata_pda.key() {
-            return Err(ErrorCode::InvalidMetaplexMetadataPda.into());
-        }
-
-        // load creator vec from metadata and cross check it for any matches with stream verified addresses
-        let stream_creator_pubkeys = &ctx.accounts.stream.verified_creator_addresses;
-        let metadata = deser_metadata(&ctx.accounts.metaplex_metadata_pda, ctx.accounts.stream.is_simulation)?;
-        let creators_vec = metadata.data.creators.as_ref().unwrap();
-        let mut found_match = false;
-        for creator_pubkey in stream_creator_pubkeys.iter() {
-            if creators_vec.iter().any(|c| (c.address == *creator_pubkey) && c.verified) {
-                found_match = true;
-                break;
-            }
-        }
-        if !found_match {
-            return Err(ErrorCode::VerifiedCreatorAddressMismatch.into());
+        // validate
+        let dao = &mut ctx.accounts.dao;
+        let proposal = &mut ctx.accounts.proposal;
+        validate_proposal_approval(dao, proposal, ctx.accounts.signer.key)?;
+        match proposal.proposal_type {
+            ProposalType::DeactivateStream => (),
+            _ => return Err(ErrorCode::MismatchProposalType.into())
         }
 
-        // proceed with connection logic
+        // check proposed deactivation stream
         let stream = &mut ctx.accounts.stream;
-        let current_timestamp = Clock::get().unwrap().unix_timestamp as u64;
+        if proposal.params.proposed_deactivation_stream != stream.key() {
+            return Err(ErrorCode::StreamMismatch.into());
+        }
 
+        // update stream
         let current_pool_balance = ctx.accounts.token_pool.amount;
+        let current_timestamp = Clock::get().unwrap().unix_timestamp as u64;
         let add_connection = false;
         update_stream_state(stream, current_pool_balance, current_timestamp, add_connection)?;
 
-        // check if is active
-        if !stream.is_active {
-            return Err(ErrorCode::StreamIsInactive.into());
-        }
-
-        // check if max connections will be exceeded
-        if (stream.num_connections + 1) > MAX_CONNECTIONS_PER_STREAM {
-            return Err(ErrorCode::MaxConnectionsPerStreamExceeded.into());
-        }
-
-        // initialize connection
-        let connection = &mut ctx.accounts.connection;
-        connection.owner_address = ctx.accounts.signer.key();
-        connection.stake_address = ctx.accounts.stake.key();
-        connection.stream_address = stream.key();
-        connection.dao_address = stream.dao_address;
-        connection.connection_timestamp = current_timestamp;
-
-        connection.total_earned = 0;
-        connection.total_claimed = 0;
-        connection.last_update_timestamp = stream.last_update_timestamp;
-        connection.is_active = true;
-
-        // update stake
-        ctx.accounts.stake.num_connections += 1;
-
-        // update stream
-        let add_connection = true;
-        update_stream_state(stream, current_pool_balance, current_timestamp, add_connection)?;
+        // deactivate and finalize
+        stream.is_active = false;
+        proposal.status = ProposalStatus::Executed;
 
         // tx fee
         let signer_handle = &ctx.accounts.signer;
-        let fee_rx_handle = &ctx.accounts.fee_receiver_address;
-        let fee_lamports = ctx.accounts.fee_controller.connect_to_stream;
+        let fee_rx_handles = ctx.remaining_accounts;
+        let fee_dao_address = Some(ctx.accounts.dao.key());
 
-        transfer_fee(signer_handle, fee_rx_handle, fee_lamports)?;
+        transfer_fee(signer_handle, &mut ctx.accounts.fee_controller, fee_rx_handles, &ctx.accounts.token_program, FeeInstruction::ExecuteDeactivateStream, fee_dao_address, 0)?;
 
 
         Ok(())
     }
 
-    pub fn claim_from_stream(
-        ctx: Context<ClaimFromStream>,
-        claim_amount: u64,
-        claim_max: bool,
+    pub fn execute_withdraw_from_stream<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteWithdrawFromStream<'info>>,
     ) -> ProgramResult {
 
+        // blocked while the dao is paused
+        validate_dao_not_paused(&ctx.accounts.dao)?;
+
+        // validate
+        let dao = &mut ctx.accounts.dao;
+        let proposal = &mut ctx.accounts.proposal;
+        validate_proposal_approval(dao, proposal, ctx.accounts.signer.key)?;
+        match proposal.proposal_type {
+            ProposalType::WithdrawFromStream => (),
+            _ => return Err(ErrorCode::MismatchProposalType.into())
+        }
+
         // update stream
         let stream = &mut ctx.accounts.stream;
         let current_pool_balance = ctx.accounts.token_pool.amount;
-        msg!("current pool balance: {:?}", current_pool_balance);
         let current_timestamp = Clock::get().unwrap().unix_timestamp as u64;
         let add_connection = false;
         update_stream_state(stream, current_pool_balance, current_timestamp, add_connection)?;
 
-        // update connection state (must always be atomically following update stream state)
-        let connection = &mut ctx.accounts.connection;
-
-        // use signed integer in case connection was updated more recently than the stream
-
-        let connection_update_lag_seconds = stream.last_update_timestamp as i64 - connection.last_update_timestamp as i64;
-        msg!("got connection_update_lag_seconds: {:?}", connection_update_lag_seconds);
-
-        if stream.is_active || (!stream.is_active && (connection_update_lag_seconds > 0)) {
-            let recently_earned = stream.stream_rate * connection_update_lag_seconds as u64; // should not be possible to overflow due to business logic
-            msg!("calculated recently_earned: {:?}", recently_earned);
-            connection.total_earned += recently_earned;
-            connection.last_update_timestamp = stream.last_update_timestamp;
+        // check proposed receiver match
+        if ctx.accounts.receiver_token_account.owner != proposal.params.proposed_withdrawal_receiver_owner {
+            return Err(ErrorCode::InvalidProposedReceiverOwner.into());
         }
 
-        msg!("calculated total earned: {:?}", connection.total_earned);
-        msg!("calculated total claimed: {:?}", connection.total_claimed);
-
-        // calculate amount to transfer
-        let available_to_claim = connection.total_earned - connection.total_claimed;
-
-        msg!("available to claim from connection: {:?}", available_to_claim);
-
-        let amount_to_transfer;
-        if !claim_max {
-            msg!("not claiming max...: {:?}", claim_max);
-            if claim_amount > available_to_claim {
-                return Err(ErrorCode::ClaimAmountExceedsAvailable.into());
-            }
-            amount_to_transfer = claim_amount;
-        } else{
-            amount_to_transfer = available_to_claim;
+        // check proposed withdraw stream
+        if proposal.params.proposed_withdrawal_stream != stream.key() {
+            return Err(ErrorCode::StreamMismatch.into());
         }
 
-        msg!("amount to transfer: {:?}", amount_to_transfer);
+        // check proposed amount <= available amount
+        let unclaimed_amount = stream.total_streamed - stream.total_claimed;
+        let available_amount = current_pool_balance - unclaimed_amount;
+        let proposed_withdrawal_amount = proposal.params.proposed_withdrawal_amount;
+        if proposed_withdrawal_amount > available_amount {
+            return Err(ErrorCode::InsufficientFunds.into());
+        }
 
         // get seeds to sign for auth_pda
-        let dao_address = ctx.accounts.dao.key();
+        let dao_address = dao.key();
         let (dao_auth_pda, bump_seed) = Pubkey::find_program_address(&[dao_address.as_ref(), DAO_AUTH_PDA_SEED], ctx.program_id);
         let seeds = &[dao_address.as_ref(), &DAO_AUTH_PDA_SEED[..], &[bump_seed]];
         let signer = &[&seeds[..]];
@@ -730,188 +1297,1592 @@ pub mod gigadao_staking {
         };
         let cpi_program = ctx.accounts.token_program.to_account_info();
         let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-        token::transfer(cpi_ctx, amount_to_transfer)?;
-
-        // update connection
-        connection.total_claimed += amount_to_transfer;
+        token::transfer(cpi_ctx, proposed_withdrawal_amount)?;
 
-        // update stream
-        stream.total_claimed += amount_to_transfer;
+        // finalize
+        proposal.status = ProposalStatus::Executed;
 
         // tx fee
         let signer_handle = &ctx.accounts.signer;
-        let fee_rx_handle = &ctx.accounts.fee_receiver_address;
-        let fee_lamports = ctx.accounts.fee_controller.claim_from_stream;
+        let fee_rx_handles = ctx.remaining_accounts;
+        let fee_dao_address = Some(ctx.accounts.dao.key());
+
+        transfer_fee(signer_handle, &mut ctx.accounts.fee_controller, fee_rx_handles, &ctx.accounts.token_program, FeeInstruction::ExecuteWithdrawFromStream, fee_dao_address, 0)?;
 
-        transfer_fee(signer_handle, fee_rx_handle, fee_lamports)?;
 
         Ok(())
     }
 
-    pub fn disconnect_from_stream(
-        ctx: Context<DisconnectFromStream>,
+    pub fn execute_update_stream_rate<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteUpdateStreamRate<'info>>,
     ) -> ProgramResult {
 
-        // TODO remember to atomically claim otherwise those funds are lost
+        // validate
+        let dao = &mut ctx.accounts.dao;
+        let proposal = &mut ctx.accounts.proposal;
+        validate_proposal_approval(dao, proposal, ctx.accounts.signer.key)?;
+        match proposal.proposal_type {
+            ProposalType::UpdateStreamRate => (),
+            _ => return Err(ErrorCode::MismatchProposalType.into())
+        }
+        if proposal.params.proposed_stream_rate > ctx.accounts.global_config.max_stream_rate {
+            return Err(ErrorCode::MaxSupportedStreamRateExceeded.into());
+        }
 
-        // update stream
+        // check proposed stream
         let stream = &mut ctx.accounts.stream;
+        if proposal.params.proposed_stream != stream.key() {
+            return Err(ErrorCode::StreamMismatch.into());
+        }
+
+        // settle accrual at the old rate before switching
         let current_pool_balance = ctx.accounts.token_pool.amount;
         let current_timestamp = Clock::get().unwrap().unix_timestamp as u64;
         let add_connection = false;
         update_stream_state(stream, current_pool_balance, current_timestamp, add_connection)?;
-        stream.num_connections -= 1;
+        msg!("Updating stream rate from {:?} to {:?}", stream.stream_rate, proposal.params.proposed_stream_rate);
+        stream.stream_rate = proposal.params.proposed_stream_rate;
 
-        // update stake
-        ctx.accounts.stake.num_connections -= 1;
-
-        // update connection
-        ctx.accounts.connection.is_active = false;
+        // finalize
+        proposal.status = ProposalStatus::Executed;
 
         // tx fee
         let signer_handle = &ctx.accounts.signer;
-        let fee_rx_handle = &ctx.accounts.fee_receiver_address;
-        let fee_lamports = ctx.accounts.fee_controller.disconnect_from_stream;
+        let fee_rx_handles = ctx.remaining_accounts;
+        let fee_dao_address = Some(ctx.accounts.dao.key());
 
-        transfer_fee(signer_handle, fee_rx_handle, fee_lamports)?;
+        transfer_fee(signer_handle, &mut ctx.accounts.fee_controller, fee_rx_handles, &ctx.accounts.token_program, FeeInstruction::ExecuteUpdateStreamRate, fee_dao_address, 0)?;
 
 
         Ok(())
     }
 
-    pub fn simulate_create_metadata(
-        ctx: Context<SimulateCreateMetadata>,
-        verified_creator_address: Pubkey,
+    pub fn execute_update_verified_creators<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteUpdateVerifiedCreators<'info>>,
     ) -> ProgramResult {
 
-        let space = spl_token_metadata::state::MAX_METADATA_LEN;
+        // validate
+        let dao = &mut ctx.accounts.dao;
+        let proposal = &mut ctx.accounts.proposal;
+        validate_proposal_approval(dao, proposal, ctx.accounts.signer.key)?;
 
-        // lookup and verify pda info
-        let nft_mint = &ctx.accounts.nft_mint.key();
-        let metadata_program_id: Pubkey = ID;
-        let metadata_seeds = &[METADATA_PREFIX, metadata_program_id.as_ref(),nft_mint.as_ref()];
-        let (pda, bump_seed) = Pubkey::find_program_address(metadata_seeds, &metadata_program_id);
-        if pda != ctx.accounts.metadata.key() {
-            panic!("wrong pda addy");
+        // check proposed stream
+        let stream = &mut ctx.accounts.stream;
+        if proposal.params.proposed_stream != stream.key() {
+            return Err(ErrorCode::StreamMismatch.into());
         }
-        let metadata_seeds = &[METADATA_PREFIX, metadata_program_id.as_ref(),nft_mint.as_ref(), &[bump_seed]];
-        let signer = &[&metadata_seeds[..]];
 
-        // create pda
-        invoke_signed(
-            &create_account(
-                ctx.accounts.signer.key,
-                &pda,
-                1.max(Rent::get()?.minimum_balance(space)),
-                space as u64,
-                &ID
-            ),
-            &[
-                ctx.accounts.signer.to_account_info().clone(),
-                ctx.accounts.metadata.to_account_info().clone(),
-                ctx.accounts.system_program.to_account_info().clone(),
-            ],
-            signer
-        )?;
+        // update creator list, connections whose nft no longer matches can be disconnected permissionlessly
+        let creator_address = proposal.params.proposed_creator_address;
+        let creator_index = stream.verified_creator_addresses.iter().position(|a| *a == creator_address);
+        match proposal.proposal_type {
+            ProposalType::AddVerifiedCreator => {
+                if creator_index.is_some() {
+                    return Err(ErrorCode::InvalidVerifiedCreatorAddress.into());
+                }
+                if stream.verified_creator_addresses.len() >= MAX_NUM_VERIFIED_CREATOR_ADDRESSES {
+                    return Err(ErrorCode::TooManyVerifiedCreatorAddresses.into());
+                }
+                stream.verified_creator_addresses.push(creator_address);
+            },
+            ProposalType::RemoveVerifiedCreator => {
+                let creator_index = creator_index.ok_or(ErrorCode::InvalidVerifiedCreatorAddress)?;
+                stream.verified_creator_addresses.remove(creator_index);
+            },
+            _ => return Err(ErrorCode::MismatchProposalType.into())
+        }
+
+        // finalize
+        proposal.status = ProposalStatus::Executed;
+
+        // tx fee
+        let signer_handle = &ctx.accounts.signer;
+        let fee_rx_handles = ctx.remaining_accounts;
+        let fee_dao_address = Some(ctx.accounts.dao.key());
+
+        transfer_fee(signer_handle, &mut ctx.accounts.fee_controller, fee_rx_handles, &ctx.accounts.token_program, FeeInstruction::ExecuteUpdateVerifiedCreators, fee_dao_address, 0)?;
+
+
+        Ok(())
+    }
+
+    pub fn execute_close_stream<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteCloseStream<'info>>,
+    ) -> ProgramResult {
+
+        // blocked while the dao is paused
+        validate_dao_not_paused(&ctx.accounts.dao)?;
+
+        // validate
+        let dao = &mut ctx.accounts.dao;
+        let proposal = &mut ctx.accounts.proposal;
+        validate_proposal_approval(dao, proposal, ctx.accounts.signer.key)?;
+        match proposal.proposal_type {
+            ProposalType::CloseStream => (),
+            _ => return Err(ErrorCode::MismatchProposalType.into())
+        }
+
+        // check proposed stream and receivers
+        let stream = &mut ctx.accounts.stream;
+        if proposal.params.proposed_stream != stream.key() {
+            return Err(ErrorCode::StreamMismatch.into());
+        }
+        if ctx.accounts.receiver_token_account.owner != proposal.params.proposed_withdrawal_receiver_owner {
+            return Err(ErrorCode::InvalidProposedReceiverOwner.into());
+        }
+        if ctx.accounts.rent_receiver.key() != proposal.params.proposed_rent_receiver {
+            return Err(ErrorCode::InvalidProposedReceiverOwner.into());
+        }
+
+        // update stream, everything streamed must have been claimed by disconnected connections
+        let current_pool_balance = ctx.accounts.token_pool.amount;
+        let current_timestamp = Clock::get().unwrap().unix_timestamp as u64;
+        let add_connection = false;
+        update_stream_state(stream, current_pool_balance, current_timestamp, add_connection)?;
+        if stream.num_connections > 0 || stream.total_streamed != stream.total_claimed {
+            return Err(ErrorCode::StreamHasOpenConnections.into());
+        }
+
+        // get seeds to sign for auth_pda
+        let dao_address = dao.key();
+        let (dao_auth_pda, bump_seed) = Pubkey::find_program_address(&[dao_address.as_ref(), DAO_AUTH_PDA_SEED], ctx.program_id);
+        let seeds = &[dao_address.as_ref(), &DAO_AUTH_PDA_SEED[..], &[bump_seed]];
+        let signer = &[&seeds[..]];
+
+        // check pda addy correct
+        if dao_auth_pda != ctx.accounts.dao_auth_pda.key() {
+            return Err(ErrorCode::InvalidAuthPda.into());
+        }
+
+        // sweep remaining pool balance
+        if current_pool_balance > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.token_pool.to_account_info(),
+                to: ctx.accounts.receiver_token_account.to_account_info(),
+                authority: ctx.accounts.dao_auth_pda.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            token::transfer(cpi_ctx, current_pool_balance)?;
+        }
+
+        // close token pool, stream account is closed to rent receiver on exit
+        let cpi_accounts = CloseAccount {
+            account: ctx.accounts.token_pool.to_account_info(),
+            destination: ctx.accounts.rent_receiver.to_account_info(),
+            authority: ctx.accounts.dao_auth_pda.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::close_account(cpi_ctx)?;
+
+        // finalize
+        stream.is_active = false;
+        proposal.status = ProposalStatus::Executed;
+
+        // tx fee
+        let signer_handle = &ctx.accounts.signer;
+        let fee_rx_handles = ctx.remaining_accounts;
+        let fee_dao_address = Some(ctx.accounts.dao.key());
+
+        transfer_fee(signer_handle, &mut ctx.accounts.fee_controller, fee_rx_handles, &ctx.accounts.token_program, FeeInstruction::ExecuteCloseStream, fee_dao_address, 0)?;
+
+
+        Ok(())
+    }
+
+    pub fn initialize_treasury<'info>(
+        ctx: Context<'_, '_, '_, 'info, InitializeTreasury<'info>>,
+    ) -> ProgramResult {
+
+        // tx fee
+        let signer_handle = &ctx.accounts.signer;
+        let fee_rx_handles = ctx.remaining_accounts;
+        let fee_dao_address = Some(ctx.accounts.dao.key());
+
+        transfer_fee(signer_handle, &mut ctx.accounts.fee_controller, fee_rx_handles, &ctx.accounts.token_program, FeeInstruction::InitializeTreasury, fee_dao_address, 0)?;
+
+
+        Ok(())
+    }
+
+    pub fn deposit_to_treasury<'info>(
+        ctx: Context<'_, '_, '_, 'info, DepositToTreasury<'info>>,
+        amount: u64,
+    ) -> ProgramResult {
+
+        // transfer
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.depositor_token_account.to_account_info(),
+            to: ctx.accounts.treasury.to_account_info(),
+            authority: ctx.accounts.signer.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+
+        // tx fee
+        let signer_handle = &ctx.accounts.signer;
+        let fee_rx_handles = ctx.remaining_accounts;
+        let fee_dao_address = Some(ctx.accounts.dao.key());
+
+        transfer_fee(signer_handle, &mut ctx.accounts.fee_controller, fee_rx_handles, &ctx.accounts.token_program, FeeInstruction::DepositToTreasury, fee_dao_address, 0)?;
+
+
+        Ok(())
+    }
+
+    pub fn execute_withdraw_from_treasury<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteWithdrawFromTreasury<'info>>,
+    ) -> ProgramResult {
+
+        // blocked while the dao is paused
+        validate_dao_not_paused(&ctx.accounts.dao)?;
+
+        // validate
+        let dao = &mut ctx.accounts.dao;
+        let proposal = &mut ctx.accounts.proposal;
+        validate_proposal_approval(dao, proposal, ctx.accounts.signer.key)?;
+        match proposal.proposal_type {
+            ProposalType::WithdrawFromTreasury => (),
+            _ => return Err(ErrorCode::MismatchProposalType.into())
+        }
+
+        // check proposed treasury and receiver match
+        if ctx.accounts.treasury.mint != proposal.params.proposed_treasury_mint {
+            return Err(ErrorCode::InvalidTreasuryMint.into());
+        }
+        if ctx.accounts.receiver_token_account.owner != proposal.params.proposed_withdrawal_receiver_owner {
+            return Err(ErrorCode::InvalidProposedReceiverOwner.into());
+        }
+
+        // check proposed amount <= treasury balance
+        let proposed_withdrawal_amount = proposal.params.proposed_withdrawal_amount;
+        if proposed_withdrawal_amount > ctx.accounts.treasury.amount {
+            return Err(ErrorCode::InsufficientFunds.into());
+        }
+
+        // get seeds to sign for auth_pda
+        let dao_address = dao.key();
+        let (dao_auth_pda, bump_seed) = Pubkey::find_program_address(&[dao_address.as_ref(), DAO_AUTH_PDA_SEED], ctx.program_id);
+        let seeds = &[dao_address.as_ref(), &DAO_AUTH_PDA_SEED[..], &[bump_seed]];
+        let signer = &[&seeds[..]];
+
+        // check pda addy correct
+        if dao_auth_pda != ctx.accounts.dao_auth_pda.key() {
+            return Err(ErrorCode::InvalidAuthPda.into());
+        }
+
+        // transfer
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.treasury.to_account_info(),
+            to: ctx.accounts.receiver_token_account.to_account_info(),
+            authority: ctx.accounts.dao_auth_pda.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, proposed_withdrawal_amount)?;
+
+        // finalize
+        proposal.status = ProposalStatus::Executed;
+
+        // tx fee
+        let signer_handle = &ctx.accounts.signer;
+        let fee_rx_handles = ctx.remaining_accounts;
+        let fee_dao_address = Some(ctx.accounts.dao.key());
+
+        transfer_fee(signer_handle, &mut ctx.accounts.fee_controller, fee_rx_handles, &ctx.accounts.token_program, FeeInstruction::ExecuteWithdrawFromTreasury, fee_dao_address, 0)?;
+
+
+        Ok(())
+    }
+
+    pub fn execute_fund_stream_from_treasury<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteFundStreamFromTreasury<'info>>,
+    ) -> ProgramResult {
+
+        // validate
+        let dao = &mut ctx.accounts.dao;
+        let proposal = &mut ctx.accounts.proposal;
+        validate_proposal_approval(dao, proposal, ctx.accounts.signer.key)?;
+        match proposal.proposal_type {
+            ProposalType::FundStreamFromTreasury => (),
+            _ => return Err(ErrorCode::MismatchProposalType.into())
+        }
+
+        // check proposed stream and treasury
+        let stream = &mut ctx.accounts.stream;
+        if proposal.params.proposed_stream != stream.key() {
+            return Err(ErrorCode::StreamMismatch.into());
+        }
+        if ctx.accounts.treasury.mint != proposal.params.proposed_treasury_mint
+            || ctx.accounts.treasury.mint != stream.token_mint_address {
+            return Err(ErrorCode::InvalidTreasuryMint.into());
+        }
+
+        // check proposed amount <= treasury balance
+        let proposed_funding_amount = proposal.params.proposed_withdrawal_amount;
+        if proposed_funding_amount > ctx.accounts.treasury.amount {
+            return Err(ErrorCode::InsufficientFunds.into());
+        }
+
+        // settle the stream against its current pool balance before funding it
+        let current_pool_balance = ctx.accounts.token_pool.amount;
+        let current_timestamp = Clock::get().unwrap().unix_timestamp as u64;
+        let add_connection = false;
+        update_stream_state(stream, current_pool_balance, current_timestamp, add_connection)?;
+
+        // get seeds to sign for auth_pda
+        let dao_address = dao.key();
+        let (dao_auth_pda, bump_seed) = Pubkey::find_program_address(&[dao_address.as_ref(), DAO_AUTH_PDA_SEED], ctx.program_id);
+        let seeds = &[dao_address.as_ref(), &DAO_AUTH_PDA_SEED[..], &[bump_seed]];
+        let signer = &[&seeds[..]];
+
+        // check pda addy correct
+        if dao_auth_pda != ctx.accounts.dao_auth_pda.key() {
+            return Err(ErrorCode::InvalidAuthPda.into());
+        }
+
+        // transfer
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.treasury.to_account_info(),
+            to: ctx.accounts.token_pool.to_account_info(),
+            authority: ctx.accounts.dao_auth_pda.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, proposed_funding_amount)?;
+
+        // finalize
+        proposal.status = ProposalStatus::Executed;
+
+        // tx fee
+        let signer_handle = &ctx.accounts.signer;
+        let fee_rx_handles = ctx.remaining_accounts;
+        let fee_dao_address = Some(ctx.accounts.dao.key());
+
+        transfer_fee(signer_handle, &mut ctx.accounts.fee_controller, fee_rx_handles, &ctx.accounts.token_program, FeeInstruction::ExecuteFundStreamFromTreasury, fee_dao_address, 0)?;
+
+
+        Ok(())
+    }
+
+    pub fn execute_custom_instruction<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteCustomInstruction<'info>>,
+    ) -> ProgramResult {
+
+        // blocked while the dao is paused, dao auth pda signed transfers count as withdrawals
+        validate_dao_not_paused(&ctx.accounts.dao)?;
+
+        // validate
+        let dao = &mut ctx.accounts.dao;
+        let proposal = &mut ctx.accounts.proposal;
+        validate_proposal_approval(dao, proposal, ctx.accounts.signer.key)?;
+        match proposal.proposal_type {
+            ProposalType::CustomInstruction => (),
+            _ => return Err(ErrorCode::MismatchProposalType.into())
+        }
+
+        // the instruction accounts followed by the target program close out the remaining accounts, fee accounts lead
+        let proposed_accounts = &proposal.params.proposed_instruction_accounts;
+        let num_cpi_accounts = proposed_accounts.len() + 1;
+        if ctx.remaining_accounts.len() < num_cpi_accounts {
+            return Err(ErrorCode::CustomInstructionAccountMismatch.into());
+        }
+        let (fee_rx_handles, cpi_account_infos) = ctx.remaining_accounts.split_at(ctx.remaining_accounts.len() - num_cpi_accounts);
+        for (proposed_account, account_info) in proposed_accounts.iter().zip(cpi_account_infos.iter()) {
+            if proposed_account.pubkey != account_info.key() {
+                return Err(ErrorCode::CustomInstructionAccountMismatch.into());
+            }
+        }
+        let program_info = &cpi_account_infos[num_cpi_accounts - 1];
+        if program_info.key() != proposal.params.proposed_instruction_program_id {
+            return Err(ErrorCode::CustomInstructionAccountMismatch.into());
+        }
+
+        // get seeds to sign for auth_pda
+        let dao_address = dao.key();
+        let (dao_auth_pda, bump_seed) = Pubkey::find_program_address(&[dao_address.as_ref(), DAO_AUTH_PDA_SEED], ctx.program_id);
+        let seeds = &[dao_address.as_ref(), &DAO_AUTH_PDA_SEED[..], &[bump_seed]];
+        let signer = &[&seeds[..]];
+
+        // check pda addy correct
+        if dao_auth_pda != ctx.accounts.dao_auth_pda.key() {
+            return Err(ErrorCode::InvalidAuthPda.into());
+        }
+
+        // stream pools hold earned but unclaimed staker balances, so the only writable dao token accounts allowed are treasuries
+        for (proposed_account, account_info) in proposed_accounts.iter().zip(cpi_account_infos.iter()) {
+            if !proposed_account.is_writable || *account_info.owner != token::ID {
+                continue;
+            }
+            if let Ok(token_account) = Account::<TokenAccount>::try_from(account_info) {
+                let (treasury_pda, _bump) = Pubkey::find_program_address(&[dao_address.as_ref(), token_account.mint.as_ref(), TREASURY_PDA_SEED], ctx.program_id);
+                if token_account.owner == dao_auth_pda && account_info.key() != treasury_pda {
+                    return Err(ErrorCode::InvalidCustomInstruction.into());
+                }
+            }
+        }
+
+        // invoke
+        let ix = Instruction {
+            program_id: proposal.params.proposed_instruction_program_id,
+            accounts: proposed_accounts
+                .iter()
+                .map(|a| AccountMeta {
+                    pubkey: a.pubkey,
+                    is_signer: a.is_signer,
+                    is_writable: a.is_writable,
+                })
+                .collect(),
+            data: proposal.params.proposed_instruction_data.clone(),
+        };
+        let mut account_infos = cpi_account_infos.to_vec();
+        account_infos.push(ctx.accounts.dao_auth_pda.to_account_info());
+        invoke_signed(&ix, &account_infos, signer)?;
+
+        // finalize
+        proposal.status = ProposalStatus::Executed;
+
+        // tx fee
+        let signer_handle = &ctx.accounts.signer;
+        let fee_dao_address = Some(ctx.accounts.dao.key());
+
+        transfer_fee(signer_handle, &mut ctx.accounts.fee_controller, fee_rx_handles, &ctx.accounts.token_program, FeeInstruction::ExecuteCustomInstruction, fee_dao_address, 0)?;
+
+
+        Ok(())
+    }
+
+    // Staker instructions
+    pub fn initialize_stake<'info>(
+        ctx: Context<'_, '_, '_, 'info, InitializeStake<'info>>,
+    ) -> ProgramResult {
+
+        // initialize stake
+        let stake = &mut ctx.accounts.stake;
+        stake.owner_address = ctx.accounts.signer.key();
+        stake.nft_mint_address = ctx.accounts.nft_mint.key();
+        stake.nft_vault_address = ctx.accounts.nft_vault.key();
+
+        stake.is_active = false;
+        stake.num_connections = 0;
+        stake.cumulative_seconds_staked = 0;
+        stake.last_stake_timestamp = 0;
+
+        // tx fee
+        let signer_handle = &ctx.accounts.sponsor;
+        let fee_rx_handles = ctx.remaining_accounts;
+        let fee_dao_address = None;
+
+        transfer_fee(signer_handle, &mut ctx.accounts.fee_controller, fee_rx_handles, &ctx.accounts.token_program, FeeInstruction::InitializeStake, fee_dao_address, 0)?;
+
+
+        Ok(())
+    }
+
+    pub fn stake_nft<'info>(
+        ctx: Context<'_, '_, '_, 'info, StakeNft<'info>>,
+    ) -> ProgramResult {
+
+        // transfer nft between token accounts
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.sender_nft_account.to_account_info(),
+            to: ctx.accounts.nft_vault.to_account_info(),
+            authority: ctx.accounts.signer.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, 1)?;
+
+        // update stake state
+        ctx.accounts.stake.is_active = true;
+        ctx.accounts.stake.last_stake_timestamp = Clock::get().unwrap().unix_timestamp as u64;
+
+        // tx fee
+        let signer_handle = &ctx.accounts.sponsor;
+        let fee_rx_handles = ctx.remaining_accounts;
+        let fee_dao_address = None;
+        let fee_discount_bps = fee_exemption_discount_bps(&ctx.accounts.fee_controller, &ctx.accounts.metaplex_metadata_pda, &ctx.accounts.nft_mint.key());
+
+        transfer_fee(signer_handle, &mut ctx.accounts.fee_controller, fee_rx_handles, &ctx.accounts.token_program, FeeInstruction::StakeNft, fee_dao_address, fee_discount_bps)?;
+
+
+        Ok(())
+    }
+
+    pub fn unstake_nft<'info>(
+        ctx: Context<'_, '_, '_, 'info, UnstakeNft<'info>>,
+    ) -> ProgramResult {
+
+        // checks stake.num_connections == 0 in account constraints
+
+        // init vault signer
+        let stake_address = ctx.accounts.stake.key();
+        let (stake_auth_pda, bump_seed) = Pubkey::find_program_address(&[stake_address.as_ref(), STAKE_AUTH_PDA_SEED], ctx.program_id);
+        let seeds = &[stake_address.as_ref(), &STAKE_AUTH_PDA_SEED[..], &[bump_seed]];
+        let signer = &[&seeds[..]];
+
+        // check pda addy correct
+        if stake_auth_pda != ctx.accounts.stake_auth_pda.key() {
+            return Err(ErrorCode::InvalidAuthPda.into());
+        }
+
+        // transfer
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.nft_vault.to_account_info(),
+            to: ctx.accounts.receiver_nft_account.to_account_info(),
+            authority: ctx.accounts.stake_auth_pda.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, 1)?;
+
+        // update stake state
+        ctx.accounts.stake.is_active = false;
+
+        // tx fee
+        let signer_handle = &ctx.accounts.signer;
+        let fee_rx_handles = ctx.remaining_accounts;
+        let fee_dao_address = None;
+
+        transfer_fee(signer_handle, &mut ctx.accounts.fee_controller, fee_rx_handles, &ctx.accounts.token_program, FeeInstruction::UnstakeNft, fee_dao_address, 0)?;
+
+
+        Ok(())
+    }
+
+    pub fn initialize_connection<'info>(
+        ctx: Context<'_, '_, '_, 'info, InitializeConnection<'info>>,
+    ) -> ProgramResult {
+        ctx.accounts.connection.is_active = false;
+
+        // tx fee
+        let signer_handle = &ctx.accounts.sponsor;
+        let fee_rx_handles = ctx.remaining_accounts;
+        let fee_dao_address = Some(ctx.accounts.stream.dao_address);
+
+        transfer_fee(signer_handle, &mut ctx.accounts.fee_controller, fee_rx_handles, &ctx.accounts.token_program, FeeInstruction::InitializeConnection, fee_dao_address, 0)?;
+
+
+        Ok(())
+    }
+
+    pub fn connect_to_stream<'info>(
+        ctx: Context<'_, '_, '_, 'info, ConnectToStream<'info>>,
+    ) -> ProgramResult {
+
+        // blocked while the protocol or dao is paused
+        validate_protocol_not_paused(&ctx.accounts.global_config)?;
+        validate_dao_not_paused(&ctx.accounts.dao)?;
+
+        // check nft carries a verified creator listed on the stream
+        let found_match = nft_has_verified_creator(&ctx.accounts.stream, &ctx.accounts.stake.nft_mint_address, &ctx.accounts.metaplex_metadata_pda)?;
+        if !found_match {
+            return Err(ErrorCode::VerifiedCreatorAddressMismatch.into());
+        }
+
+        // proceed with connection logic
+        let stream = &mut ctx.accounts.stream;
+        let current_timestamp = Clock::get().unwrap().unix_timestamp as u64;
+
+        let current_pool_balance = ctx.accounts.token_pool.amount;
+        let add_connection = false;
+        update_stream_state(stream, current_pool_balance, current_timestamp, add_connection)?;
+
+        // check if is active
+        if !stream.is_active {
+            return Err(ErrorCode::StreamIsInactive.into());
+        }
+
+        // check if max connections will be exceeded
+        if (stream.num_connections + 1) > ctx.accounts.global_config.max_connections_per_stream {
+            return Err(ErrorCode::MaxConnectionsPerStreamExceeded.into());
+        }
+
+        // initialize connection
+        let connection = &mut ctx.accounts.connection;
+        connection.owner_address = ctx.accounts.signer.key();
+        connection.stake_address = ctx.accounts.stake.key();
+        connection.stream_address = stream.key();
+        connection.dao_address = stream.dao_address;
+        connection.connection_timestamp = current_timestamp;
+
+        // totals stay cumulative across reconnects so unpaid earnings from a paused disconnect survive
+        connection.earned_per_connection_snapshot = stream.earned_per_connection;
+        connection.last_update_timestamp = stream.last_update_timestamp;
+        connection.is_active = true;
+
+        // update stake
+        ctx.accounts.stake.num_connections += 1;
+
+        // update stream
+        let add_connection = true;
+        update_stream_state(stream, current_pool_balance, current_timestamp, add_connection)?;
+
+        // tx fee
+        let signer_handle = &ctx.accounts.sponsor;
+        let fee_rx_handles = ctx.remaining_accounts;
+        let fee_dao_address = Some(ctx.accounts.stream.dao_address);
+        let fee_discount_bps = fee_exemption_discount_bps(&ctx.accounts.fee_controller, &ctx.accounts.metaplex_metadata_pda, &ctx.accounts.stake.nft_mint_address);
+
+        transfer_fee(signer_handle, &mut ctx.accounts.fee_controller, fee_rx_handles, &ctx.accounts.token_program, FeeInstruction::ConnectToStream, fee_dao_address, fee_discount_bps)?;
+
+
+        Ok(())
+    }
+
+    pub fn claim_from_stream<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimFromStream<'info>>,
+        claim_amount: u64,
+        claim_max: bool,
+    ) -> ProgramResult {
+
+        // blocked while the protocol or dao is paused
+        validate_protocol_not_paused(&ctx.accounts.global_config)?;
+        validate_dao_not_paused(&ctx.accounts.dao)?;
+
+        // update stream
+        let stream = &mut ctx.accounts.stream;
+        let current_pool_balance = ctx.accounts.token_pool.amount;
+        msg!("current pool balance: {:?}", current_pool_balance);
+        let current_timestamp = Clock::get().unwrap().unix_timestamp as u64;
+        let add_connection = false;
+        update_stream_state(stream, current_pool_balance, current_timestamp, add_connection)?;
+
+        // update connection state (must always be atomically following update stream state),
+        // disconnected connections only claim what was settled at disconnect
+        let connection = &mut ctx.accounts.connection;
+        if connection.is_active {
+            update_connection_state(stream, connection);
+        }
+
+        msg!("calculated total earned: {:?}", connection.total_earned);
+        msg!("calculated total claimed: {:?}", connection.total_claimed);
+
+        // calculate amount to transfer
+        let available_to_claim = connection.total_earned - connection.total_claimed;
+
+        msg!("available to claim from connection: {:?}", available_to_claim);
+
+        let amount_to_transfer;
+        if !claim_max {
+            msg!("not claiming max...: {:?}", claim_max);
+            if claim_amount > available_to_claim {
+                return Err(ErrorCode::ClaimAmountExceedsAvailable.into());
+            }
+            amount_to_transfer = claim_amount;
+        } else{
+            amount_to_transfer = available_to_claim;
+        }
+
+        msg!("amount to transfer: {:?}", amount_to_transfer);
+
+        // get seeds to sign for auth_pda
+        let dao_address = ctx.accounts.dao.key();
+        let (dao_auth_pda, bump_seed) = Pubkey::find_program_address(&[dao_address.as_ref(), DAO_AUTH_PDA_SEED], ctx.program_id);
+        let seeds = &[dao_address.as_ref(), &DAO_AUTH_PDA_SEED[..], &[bump_seed]];
+        let signer = &[&seeds[..]];
+
+        // check pda addy correct
+        if dao_auth_pda != ctx.accounts.dao_auth_pda.key() {
+            return Err(ErrorCode::InvalidAuthPda.into());
+        }
+
+        // transfer, the full amount counts as claimed since it all leaves the pool
+        transfer_from_token_pool(
+            &ctx.accounts.token_pool,
+            &ctx.accounts.receiver_token_account,
+            &ctx.accounts.protocol_fee_token_account,
+            &ctx.accounts.dao_auth_pda,
+            &ctx.accounts.token_program,
+            &ctx.accounts.fee_controller,
+            signer,
+            amount_to_transfer,
+        )?;
+
+        // update connection
+        connection.total_claimed += amount_to_transfer;
+
+        // update stream
+        stream.total_claimed += amount_to_transfer;
+
+        // tx fee
+        let signer_handle = &ctx.accounts.sponsor;
+        let fee_rx_handles = ctx.remaining_accounts;
+        let fee_dao_address = Some(ctx.accounts.dao.key());
+        let fee_discount_bps = fee_exemption_discount_bps(&ctx.accounts.fee_controller, &ctx.accounts.metaplex_metadata_pda, &ctx.accounts.stake.nft_mint_address);
+
+        transfer_fee(signer_handle, &mut ctx.accounts.fee_controller, fee_rx_handles, &ctx.accounts.token_program, FeeInstruction::ClaimFromStream, fee_dao_address, fee_discount_bps)?;
+
+        Ok(())
+    }
+
+    pub fn disconnect_from_stream<'info>(
+        ctx: Context<'_, '_, '_, 'info, DisconnectFromStream<'info>>,
+    ) -> ProgramResult {
+
+        // update stream
+        let stream = &mut ctx.accounts.stream;
+        let current_pool_balance = ctx.accounts.token_pool.amount;
+        let current_timestamp = Clock::get().unwrap().unix_timestamp as u64;
+        let add_connection = false;
+        update_stream_state(stream, current_pool_balance, current_timestamp, add_connection)?;
+
+        // update connection state (must always be atomically following update stream state)
+        let connection = &mut ctx.accounts.connection;
+        update_connection_state(stream, connection);
+        let available_to_claim = connection.total_earned - connection.total_claimed;
+        msg!("claiming before disconnect: {:?}", available_to_claim);
+
+        // atomically claim unless paused, unpaid earnings then stay on the connection and can be claimed after unpausing
+        if !ctx.accounts.dao.paused {
+            // get seeds to sign for auth_pda
+            let dao_address = ctx.accounts.dao.key();
+            let (dao_auth_pda, bump_seed) = Pubkey::find_program_address(&[dao_address.as_ref(), DAO_AUTH_PDA_SEED], ctx.program_id);
+            let seeds = &[dao_address.as_ref(), &DAO_AUTH_PDA_SEED[..], &[bump_seed]];
+            let signer = &[&seeds[..]];
+
+            // check pda addy correct
+            if dao_auth_pda != ctx.accounts.dao_auth_pda.key() {
+                return Err(ErrorCode::InvalidAuthPda.into());
+            }
+
+            // transfer
+            transfer_from_token_pool(
+                &ctx.accounts.token_pool,
+                &ctx.accounts.receiver_token_account,
+                &ctx.accounts.protocol_fee_token_account,
+                &ctx.accounts.dao_auth_pda,
+                &ctx.accounts.token_program,
+                &ctx.accounts.fee_controller,
+                signer,
+                available_to_claim,
+            )?;
+            connection.total_claimed += available_to_claim;
+            stream.total_claimed += available_to_claim;
+        }
+
+        // disconnect
+        stream.num_connections -= 1;
+        ctx.accounts.stake.num_connections -= 1;
+        connection.is_active = false;
+
+        // tx fee
+        let signer_handle = &ctx.accounts.signer;
+        let fee_rx_handles = ctx.remaining_accounts;
+        let fee_dao_address = Some(ctx.accounts.dao.key());
+
+        transfer_fee(signer_handle, &mut ctx.accounts.fee_controller, fee_rx_handles, &ctx.accounts.token_program, FeeInstruction::DisconnectFromStream, fee_dao_address, 0)?;
+
+
+        Ok(())
+    }
+
+    pub fn disconnect_unverified_connection<'info>(
+        ctx: Context<'_, '_, '_, 'info, DisconnectUnverifiedConnection<'info>>,
+    ) -> ProgramResult {
+
+        // only connections whose nft lost its verified creator on the stream can be force disconnected
+        let found_match = nft_has_verified_creator(&ctx.accounts.stream, &ctx.accounts.stake.nft_mint_address, &ctx.accounts.metaplex_metadata_pda)?;
+        if found_match {
+            return Err(ErrorCode::ConnectionStillVerified.into());
+        }
+
+        // update stream
+        let stream = &mut ctx.accounts.stream;
+        let current_pool_balance = ctx.accounts.token_pool.amount;
+        let current_timestamp = Clock::get().unwrap().unix_timestamp as u64;
+        let add_connection = false;
+        update_stream_state(stream, current_pool_balance, current_timestamp, add_connection)?;
+
+        // update connection state (must always be atomically following update stream state)
+        let connection = &mut ctx.accounts.connection;
+        update_connection_state(stream, connection);
+        let available_to_claim = connection.total_earned - connection.total_claimed;
+        msg!("paying out to connection owner: {:?}", available_to_claim);
+
+        // pay unless paused, unpaid earnings then stay on the connection and can be claimed after unpausing
+        if !ctx.accounts.dao.paused {
+            // get seeds to sign for auth_pda
+            let dao_address = ctx.accounts.dao.key();
+            let (dao_auth_pda, bump_seed) = Pubkey::find_program_address(&[dao_address.as_ref(), DAO_AUTH_PDA_SEED], ctx.program_id);
+            let seeds = &[dao_address.as_ref(), &DAO_AUTH_PDA_SEED[..], &[bump_seed]];
+            let signer = &[&seeds[..]];
+
+            // check pda addy correct
+            if dao_auth_pda != ctx.accounts.dao_auth_pda.key() {
+                return Err(ErrorCode::InvalidAuthPda.into());
+            }
+
+            // pay accrued earnings to the connection owner so nothing is lost
+            transfer_from_token_pool(
+                &ctx.accounts.token_pool,
+                &ctx.accounts.receiver_token_account,
+                &ctx.accounts.protocol_fee_token_account,
+                &ctx.accounts.dao_auth_pda,
+                &ctx.accounts.token_program,
+                &ctx.accounts.fee_controller,
+                signer,
+                available_to_claim,
+            )?;
+            connection.total_claimed += available_to_claim;
+            stream.total_claimed += available_to_claim;
+        }
+
+        // disconnect
+        stream.num_connections -= 1;
+        ctx.accounts.stake.num_connections -= 1;
+        connection.is_active = false;
+
+        // tx fee
+        let signer_handle = &ctx.accounts.signer;
+        let fee_rx_handles = ctx.remaining_accounts;
+        let fee_dao_address = Some(ctx.accounts.dao.key());
+
+        transfer_fee(signer_handle, &mut ctx.accounts.fee_controller, fee_rx_handles, &ctx.accounts.token_program, FeeInstruction::DisconnectUnverifiedConnection, fee_dao_address, 0)?;
+
+
+        Ok(())
+    }
+
+    pub fn simulate_create_metadata(
+        ctx: Context<SimulateCreateMetadata>,
+        verified_creator_address: Pubkey,
+    ) -> ProgramResult {
+
+        let space = spl_token_metadata::state::MAX_METADATA_LEN;
+
+        // lookup and verify pda info
+        let nft_mint = &ctx.accounts.nft_mint.key();
+        let metadata_program_id: Pubkey = ID;
+        let metadata_seeds = &[METADATA_PREFIX, metadata_program_id.as_ref(),nft_mint.as_ref()];
+        let (pda, bump_seed) = Pubkey::find_program_address(metadata_seeds, &metadata_program_id);
+        if pda != ctx.accounts.metadata.key() {
+            panic!("wrong pda addy");
+        }
+        let metadata_seeds = &[METADATA_PREFIX, metadata_program_id.as_ref(),nft_mint.as_ref(), &[bump_seed]];
+        let signer = &[&metadata_seeds[..]];
+
+        // create pda
+        invoke_signed(
+            &create_account(
+                ctx.accounts.signer.key,
+                &pda,
+                1.max(Rent::get()?.minimum_balance(space)),
+                space as u64,
+                &ID
+            ),
+            &[
+                ctx.accounts.signer.to_account_info().clone(),
+                ctx.accounts.metadata.to_account_info().clone(),
+                ctx.accounts.system_program.to_account_info().clone(),
+            ],
+            signer
+        )?;
+
+        let creator = spl_token_metadata::state::Creator {
+            address: verified_creator_address,
+            verified: true,
+            share: 1,
+        };
+        let creators = vec![creator];
+        let mut metadata = spl_token_metadata::state::Metadata::from_account_info(&ctx.accounts.metadata)?;
+        let data = spl_token_metadata::state::Data {
+            name: "".to_string(),
+            symbol: "".to_string(),
+            uri: "wutup gangsta mein".to_string(),
+            seller_fee_basis_points: 666,
+            creators: Some(creators),
+        };
+        metadata.mint = ctx.accounts.nft_mint.key();
+        metadata.key = spl_token_metadata::state::Key::MetadataV1;
+        metadata.data = data;
+        metadata.is_mutable = true;
+        metadata.update_authority = ctx.accounts.signer.key();
+
+        spl_token_metadata::utils::puff_out_data_fields(&mut metadata);
+        metadata.serialize(&mut *ctx.accounts.metadata.try_borrow_mut_data().unwrap())?;
+
+        Ok(())
+    }
+
+    // pub fn tmp_override_stream(
+    //     ctx: Context<ReactivateStream>,
+    // ) -> ProgramResult {
+    //
+    //     // check that signer is a dao councillor
+    //     let _owner_index = ctx.accounts.dao.councillors
+    //         .iter()
+    //         .position(|a| a == ctx.accounts.signer.key)
+    //         .ok_or(ErrorCode::InvalidCouncillor)?;
+    //
+    //     // calculate recent streamed
+    //     let stream = &mut ctx.accounts.stream;
+    //
+    //     let current_timestamp = Clock::get().unwrap().unix_timestamp as u64;
+    //     stream.last_update_timestamp = current_timestamp;
+    //     stream.total_streamed = 600_000_0000;
+    //
+    //     Ok(())
+    // }
+
+}
+
+// Singleton system config
+#[derive(Accounts)]
+#[instruction(fee_authorities: Vec<Pubkey>, fee_approval_threshold: u64)]
+pub struct InitializeFeeController<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+    init,
+    seeds = [FEE_CONTROLLER_PDA_SEED],
+    bump,
+    payer = signer,
+    space = MAX_FEE_CONTROLLER_ACCOUNT_LEN)]
+    pub fee_controller: Account<'info, FeeController>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeGlobalConfig<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+    init,
+    seeds = [GLOBAL_CONFIG_PDA_SEED],
+    bump,
+    payer = signer,
+    space = MAX_GLOBAL_CONFIG_ACCOUNT_LEN)]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(
+    seeds = [FEE_CONTROLLER_PDA_SEED],
+    bump)]
+    pub fee_controller: Box<Account<'info, FeeController>>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateGlobalConfig<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+    mut,
+    seeds = [GLOBAL_CONFIG_PDA_SEED],
+    bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(
+    seeds = [FEE_CONTROLLER_PDA_SEED],
+    bump)]
+    pub fee_controller: Box<Account<'info, FeeController>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(fee_instruction_int: u8, new_fee_amount_lamports: u64, new_fee_schedule: Option<Vec<u64>>)]
+pub struct UpdateFeeController<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+    mut,
+    seeds = [FEE_CONTROLLER_PDA_SEED],
+    bump)]
+    pub fee_controller: Account<'info, FeeController>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+#[instruction(fee_increase_delay_seconds: u64)]
+pub struct UpdateFeeIncreaseDelay<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+    mut,
+    seeds = [FEE_CONTROLLER_PDA_SEED],
+    bump)]
+    pub fee_controller: Account<'info, FeeController>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApplyPendingFees<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+    mut,
+    seeds = [FEE_CONTROLLER_PDA_SEED],
+    bump)]
+    pub fee_controller: Account<'info, FeeController>,
+}
+
+#[derive(Accounts)]
+#[instruction(new_fee_authorities: Vec<Pubkey>, new_fee_approval_threshold: u64)]
+pub struct ProposeFeeAuthority<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+    mut,
+    seeds = [FEE_CONTROLLER_PDA_SEED],
+    bump)]
+    pub fee_controller: Account<'info, FeeController>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(fee_receivers: Vec<FeeReceiver>)]
+pub struct UpdateFeeReceivers<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+    mut,
+    seeds = [FEE_CONTROLLER_PDA_SEED],
+    bump)]
+    pub fee_controller: Account<'info, FeeController>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptFeeAuthority<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+    mut,
+    seeds = [FEE_CONTROLLER_PDA_SEED],
+    bump)]
+    pub fee_controller: Account<'info, FeeController>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ResetFeeStats<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+    mut,
+    seeds = [FEE_CONTROLLER_PDA_SEED],
+    bump)]
+    pub fee_controller: Account<'info, FeeController>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(fee_exemptions: Vec<FeeExemption>)]
+pub struct UpdateFeeExemptions<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+    mut,
+    seeds = [FEE_CONTROLLER_PDA_SEED],
+    bump)]
+    pub fee_controller: Account<'info, FeeController>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(claim_fee_bps: u16, protocol_fee_owner: Pubkey)]
+pub struct UpdateClaimFee<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+    mut,
+    seeds = [FEE_CONTROLLER_PDA_SEED],
+    bump)]
+    pub fee_controller: Account<'info, FeeController>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeFeeVault<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub fee_mint: Account<'info, Mint>,
+    #[account(
+    init,
+    token::mint = fee_mint,
+    token::authority = fee_controller,
+    seeds = [fee_mint.key().as_ref(), FEE_VAULT_PDA_SEED],
+    bump,
+    payer = signer)]
+    pub fee_vault: Account<'info, TokenAccount>,
+    #[account(
+    seeds = [FEE_CONTROLLER_PDA_SEED],
+    bump)]
+    pub fee_controller: Box<Account<'info, FeeController>>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+#[instruction(fee_mint: Option<Pubkey>, token_fees: Vec<u64>)]
+pub struct UpdateFeeMint<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+    mut,
+    seeds = [FEE_CONTROLLER_PDA_SEED],
+    bump)]
+    pub fee_controller: Account<'info, FeeController>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(amount: u64)]
+pub struct WithdrawFromFeeVault<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+    mut,
+    seeds = [fee_vault.mint.as_ref(), FEE_VAULT_PDA_SEED],
+    bump)]
+    pub fee_vault: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub receiver_token_account: Account<'info, TokenAccount>,
+    #[account(
+    seeds = [FEE_CONTROLLER_PDA_SEED],
+    bump)]
+    pub fee_controller: Box<Account<'info, FeeController>>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeFeeOverride<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub dao: Box<Account<'info, Dao>>,
+    #[account(
+    init,
+    seeds = [dao.key().as_ref(), FEE_OVERRIDE_PDA_SEED],
+    bump,
+    payer = signer,
+    space = MAX_FEE_OVERRIDE_ACCOUNT_LEN)]
+    pub fee_override: Account<'info, FeeOverride>,
+    #[account(
+    seeds = [FEE_CONTROLLER_PDA_SEED],
+    bump)]
+    pub fee_controller: Box<Account<'info, FeeController>>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+#[instruction(fee_instruction_int: u8, fee_bps: u32)]
+pub struct UpdateFeeOverride<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub dao: Box<Account<'info, Dao>>,
+    #[account(
+    mut,
+    seeds = [dao.key().as_ref(), FEE_OVERRIDE_PDA_SEED],
+    bump,
+    constraint = fee_override.dao_address == dao.key())]
+    pub fee_override: Account<'info, FeeOverride>,
+    #[account(
+    seeds = [FEE_CONTROLLER_PDA_SEED],
+    bump)]
+    pub fee_controller: Box<Account<'info, FeeController>>,
+    pub system_program: Program<'info, System>,
+}
+
+// DAO instruction contexts
+#[derive(Accounts)]
+#[instruction(name: String, metadata: DaoMetadata, governance_config: DaoGovernanceConfig)]
+pub struct InitializeDao<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        init,
+        seeds = [signer.key().as_ref(), DAO_PDA_SEED, name.as_bytes()],
+        bump,
+        payer = signer,
+        space = MAX_DAO_ACCOUNT_LEN)]
+    pub dao: Account<'info, Dao>,
+    #[account(
+        init,
+        seeds = [dao.key().as_ref(), DAO_AUTH_PDA_SEED],
+        bump,
+        payer = signer,
+        space = MIN_ACCOUNT_LEN)]
+    pub dao_auth_pda: Account<'info, AuthAccount>,
+    #[account(
+    mut,
+    seeds = [FEE_CONTROLLER_PDA_SEED],
+    bump)]
+    pub fee_controller: Box<Account<'info, FeeController>>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+#[instruction(verified_creator_addresses: Vec<Pubkey>, stream_rate: u64, is_simulation: bool)]
+pub struct InitializeStream<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        init,
+        seeds = [dao.key().as_ref(), STREAM_PDA_SEED, &dao.num_streams.to_le_bytes()],
+        bump,
+        payer = signer,
+        space = MAX_STREAM_ACCOUNT_LEN)]
+    pub stream: Account<'info, Stream>,
+    #[account(mut)]
+    pub dao: Account<'info, Dao>,
+    pub token_mint: Account<'info, Mint>,
+    #[account(
+        init,
+        token::mint = token_mint,
+        token::authority = dao_auth_pda,
+        seeds = [stream.key().as_ref(), TOKEN_POOL_PDA_SEED],
+        bump,
+        payer = signer)]
+    pub token_pool: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [dao.key().as_ref(), DAO_AUTH_PDA_SEED],
+        bump)]
+    pub dao_auth_pda: Account<'info, AuthAccount>,
+    #[account(
+    seeds = [GLOBAL_CONFIG_PDA_SEED],
+    bump)]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+    #[account(
+    mut,
+    seeds = [FEE_CONTROLLER_PDA_SEED],
+    bump)]
+    pub fee_controller: Box<Account<'info, FeeController>>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct ReactivateStream<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+    mut,
+    constraint = stream.dao_address == dao.key(),
+    constraint = stream.is_active == false,
+    )]
+    pub stream: Box<Account<'info, Stream>>,
+    #[account(mut)]
+    pub dao: Box<Account<'info, Dao>>,
+    #[account(
+    mut,
+    seeds = [stream.key().as_ref(), TOKEN_POOL_PDA_SEED],
+    bump
+    )]
+    pub token_pool: Account<'info, TokenAccount>,
+    #[account(
+    seeds = [GLOBAL_CONFIG_PDA_SEED],
+    bump)]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+    #[account(
+    mut,
+    seeds = [FEE_CONTROLLER_PDA_SEED],
+    bump)]
+    pub fee_controller: Box<Account<'info, FeeController>>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_type_int: u8, params: ProposalParams)]
+pub struct ProposeDaoCommand<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(mut)]
+    pub dao: Box<Account<'info, Dao>>,
+    #[account(
+        init,
+        seeds = [dao.key().as_ref(), PROPOSAL_PDA_SEED, &dao.num_proposals.to_le_bytes()],
+        bump,
+        payer = signer,
+        space = MAX_PROPOSAL_ACCOUNT_LEN)]
+    pub proposal: Box<Account<'info, Proposal>>,
+    #[account(
+    mut,
+    seeds = [FEE_CONTROLLER_PDA_SEED],
+    bump)]
+    pub fee_controller: Box<Account<'info, FeeController>>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
 
-        let creator = spl_token_metadata::state::Creator {
-            address: verified_creator_address,
-            verified: true,
-            share: 1,
-        };
-        let creators = vec![creator];
-        let mut metadata = spl_token_metadata::state::Metadata::from_account_info(&ctx.accounts.metadata)?;
-        let data = spl_token_metadata::state::Data {
-            name: "".to_string(),
-            symbol: "".to_string(),
-            uri: "wutup gangsta mein".to_string(),
-            seller_fee_basis_points: 666,
-            creators: Some(creators),
-        };
-        metadata.mint = ctx.accounts.nft_mint.key();
-        metadata.key = spl_token_metadata::state::Key::MetadataV1;
-        metadata.data = data;
-        metadata.is_mutable = true;
-        metadata.update_authority = ctx.accounts.signer.key();
+#[derive(Accounts)]
+#[instruction(expected_content_hash: [u8; 32])]
+pub struct ApproveDaoCommand<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(mut)]
+    pub dao: Box<Account<'info, Dao>>,
+    #[account(
+        mut,
+        seeds = [dao.key().as_ref(), PROPOSAL_PDA_SEED, &proposal.proposal_index.to_le_bytes()],
+        bump,
+        constraint = proposal.dao_address == dao.key())]
+    pub proposal: Box<Account<'info, Proposal>>,
+    #[account(
+    mut,
+    seeds = [FEE_CONTROLLER_PDA_SEED],
+    bump)]
+    pub fee_controller: Box<Account<'info, FeeController>>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
 
-        spl_token_metadata::utils::puff_out_data_fields(&mut metadata);
-        metadata.serialize(&mut *ctx.accounts.metadata.try_borrow_mut_data().unwrap())?;
+#[derive(Accounts)]
+#[instruction()]
+pub struct ExecuteUpdateDaoMultisig<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(mut)]
+    pub dao: Box<Account<'info, Dao>>,
+    #[account(
+        mut,
+        seeds = [dao.key().as_ref(), PROPOSAL_PDA_SEED, &proposal.proposal_index.to_le_bytes()],
+        bump,
+        constraint = proposal.dao_address == dao.key())]
+    pub proposal: Box<Account<'info, Proposal>>,
+    #[account(
+    mut,
+    seeds = [FEE_CONTROLLER_PDA_SEED],
+    bump)]
+    pub fee_controller: Box<Account<'info, FeeController>>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
 
-        Ok(())
-    }
+#[derive(Accounts)]
+pub struct RevokeApproval<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(mut)]
+    pub dao: Box<Account<'info, Dao>>,
+    #[account(
+        mut,
+        seeds = [dao.key().as_ref(), PROPOSAL_PDA_SEED, &proposal.proposal_index.to_le_bytes()],
+        bump,
+        constraint = proposal.dao_address == dao.key())]
+    pub proposal: Box<Account<'info, Proposal>>,
+    #[account(
+    mut,
+    seeds = [FEE_CONTROLLER_PDA_SEED],
+    bump)]
+    pub fee_controller: Box<Account<'info, FeeController>>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
 
-    // pub fn tmp_override_stream(
-    //     ctx: Context<ReactivateStream>,
-    // ) -> ProgramResult {
-    //
-    //     // check that signer is a dao councillor
-    //     let _owner_index = ctx.accounts.dao.councillors
-    //         .iter()
-    //         .position(|a| a == ctx.accounts.signer.key)
-    //         .ok_or(ErrorCode::InvalidCouncillor)?;
-    //
-    //     // calculate recent streamed
-    //     let stream = &mut ctx.accounts.stream;
-    //
-    //     let current_timestamp = Clock::get().unwrap().unix_timestamp as u64;
-    //     stream.last_update_timestamp = current_timestamp;
-    //     stream.total_streamed = 600_000_0000;
-    //
-    //     Ok(())
-    // }
+#[derive(Accounts)]
+pub struct CancelProposal<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(mut)]
+    pub dao: Box<Account<'info, Dao>>,
+    #[account(
+        mut,
+        seeds = [dao.key().as_ref(), PROPOSAL_PDA_SEED, &proposal.proposal_index.to_le_bytes()],
+        bump,
+        constraint = proposal.dao_address == dao.key())]
+    pub proposal: Box<Account<'info, Proposal>>,
+    #[account(
+    mut,
+    seeds = [FEE_CONTROLLER_PDA_SEED],
+    bump)]
+    pub fee_controller: Box<Account<'info, FeeController>>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
 
+#[derive(Accounts)]
+pub struct ExpireProposal<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub dao: Box<Account<'info, Dao>>,
+    #[account(
+        mut,
+        seeds = [dao.key().as_ref(), PROPOSAL_PDA_SEED, &proposal.proposal_index.to_le_bytes()],
+        bump,
+        constraint = proposal.dao_address == dao.key())]
+    pub proposal: Box<Account<'info, Proposal>>,
 }
 
-// Singleton system config
 #[derive(Accounts)]
-pub struct InitializeFeeController<'info> {
+pub struct PauseDao<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
+    #[account(mut)]
+    pub dao: Box<Account<'info, Dao>>,
     #[account(
-    init,
+    mut,
+    seeds = [FEE_CONTROLLER_PDA_SEED],
+    bump)]
+    pub fee_controller: Box<Account<'info, FeeController>>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteUnpauseDao<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(mut)]
+    pub dao: Box<Account<'info, Dao>>,
+    #[account(
+        mut,
+        seeds = [dao.key().as_ref(), PROPOSAL_PDA_SEED, &proposal.proposal_index.to_le_bytes()],
+        bump,
+        constraint = proposal.dao_address == dao.key())]
+    pub proposal: Box<Account<'info, Proposal>>,
+    #[account(
+    mut,
+    seeds = [FEE_CONTROLLER_PDA_SEED],
+    bump)]
+    pub fee_controller: Box<Account<'info, FeeController>>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteUpdateDaoRoles<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(mut)]
+    pub dao: Box<Account<'info, Dao>>,
+    #[account(
+        mut,
+        seeds = [dao.key().as_ref(), PROPOSAL_PDA_SEED, &proposal.proposal_index.to_le_bytes()],
+        bump,
+        constraint = proposal.dao_address == dao.key())]
+    pub proposal: Box<Account<'info, Proposal>>,
+    #[account(
+    mut,
+    seeds = [FEE_CONTROLLER_PDA_SEED],
+    bump)]
+    pub fee_controller: Box<Account<'info, FeeController>>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteUpdateDaoMetadata<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(mut)]
+    pub dao: Box<Account<'info, Dao>>,
+    #[account(
+        mut,
+        seeds = [dao.key().as_ref(), PROPOSAL_PDA_SEED, &proposal.proposal_index.to_le_bytes()],
+        bump,
+        constraint = proposal.dao_address == dao.key())]
+    pub proposal: Box<Account<'info, Proposal>>,
+    #[account(
+    mut,
+    seeds = [FEE_CONTROLLER_PDA_SEED],
+    bump)]
+    pub fee_controller: Box<Account<'info, FeeController>>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteUpdateDaoConfig<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(mut)]
+    pub dao: Box<Account<'info, Dao>>,
+    #[account(
+        mut,
+        seeds = [dao.key().as_ref(), PROPOSAL_PDA_SEED, &proposal.proposal_index.to_le_bytes()],
+        bump,
+        constraint = proposal.dao_address == dao.key())]
+    pub proposal: Box<Account<'info, Proposal>>,
+    #[account(
+    mut,
     seeds = [FEE_CONTROLLER_PDA_SEED],
-    bump,
-    payer = signer,
-    space = MAX_FEE_CONTROLLER_ACCOUNT_LEN)]
-    pub fee_controller: Account<'info, FeeController>,
+    bump)]
+    pub fee_controller: Box<Account<'info, FeeController>>,
     pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
     pub rent: Sysvar<'info, Rent>,
 }
 
 #[derive(Accounts)]
-#[instruction(instruction_name: String, new_fee_amount_lamports: u64)]
-pub struct UpdateFeeController<'info> {
+pub struct ExecuteDeactivateStream<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
+    #[account(mut)]
+    pub dao: Box<Account<'info, Dao>>,
+    #[account(
+        mut,
+        seeds = [dao.key().as_ref(), PROPOSAL_PDA_SEED, &proposal.proposal_index.to_le_bytes()],
+        bump,
+        constraint = proposal.dao_address == dao.key())]
+    pub proposal: Box<Account<'info, Proposal>>,
+    #[account(mut, constraint = stream.dao_address == dao.key())]
+    pub stream: Account<'info, Stream>,
+    #[account(
+        mut,
+        seeds = [stream.key().as_ref(), TOKEN_POOL_PDA_SEED],
+        bump
+        )]
+    pub token_pool: Account<'info, TokenAccount>,
     #[account(
     mut,
     seeds = [FEE_CONTROLLER_PDA_SEED],
     bump)]
-    pub fee_controller: Account<'info, FeeController>,
+    pub fee_controller: Box<Account<'info, FeeController>>,
     pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
     pub rent: Sysvar<'info, Rent>,
 }
 
-// DAO instruction contexts
 #[derive(Accounts)]
-#[instruction(councillors: Vec<Pubkey>, approval_threshold: u64)]
-pub struct InitializeDao<'info> {
+#[instruction()]
+pub struct ExecuteWithdrawFromStream<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
+    #[account(mut)]
+    pub dao: Box<Account<'info, Dao>>,
     #[account(
-        init,
-        payer = signer,
-        space = MAX_DAO_ACCOUNT_LEN)]
-    pub dao: Account<'info, Dao>,
+        mut,
+        seeds = [dao.key().as_ref(), PROPOSAL_PDA_SEED, &proposal.proposal_index.to_le_bytes()],
+        bump,
+        constraint = proposal.dao_address == dao.key())]
+    pub proposal: Box<Account<'info, Proposal>>,
+    #[account(mut, constraint = stream.dao_address == dao.key())]
+    pub stream: Account<'info, Stream>,
     #[account(
-        init,
+        mut,
+        seeds = [stream.key().as_ref(), TOKEN_POOL_PDA_SEED],
+        bump
+        )]
+    pub token_pool: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub receiver_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
         seeds = [dao.key().as_ref(), DAO_AUTH_PDA_SEED],
-        bump,
-        payer = signer,
-        space = MIN_ACCOUNT_LEN)]
+        bump)]
     pub dao_auth_pda: Account<'info, AuthAccount>,
-    #[account(mut)]
-    pub fee_receiver_address: AccountInfo<'info>,
     #[account(
     mut,
     seeds = [FEE_CONTROLLER_PDA_SEED],
@@ -923,33 +2894,29 @@ pub struct InitializeDao<'info> {
 }
 
 #[derive(Accounts)]
-#[instruction(verified_creator_addresses: Vec<Pubkey>, stream_rate: u64, is_simulation: bool)]
-pub struct InitializeStream<'info> {
+pub struct ExecuteUpdateStreamRate<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
-    #[account(
-        init,
-        payer = signer,
-        space = MAX_STREAM_ACCOUNT_LEN)]
-    pub stream: Account<'info, Stream>,
     #[account(mut)]
-    pub dao: Account<'info, Dao>,
-    pub token_mint: Account<'info, Mint>,
+    pub dao: Box<Account<'info, Dao>>,
     #[account(
-        init,
-        token::mint = token_mint,
-        token::authority = dao_auth_pda,
-        seeds = [stream.key().as_ref(), TOKEN_POOL_PDA_SEED],
+        mut,
+        seeds = [dao.key().as_ref(), PROPOSAL_PDA_SEED, &proposal.proposal_index.to_le_bytes()],
         bump,
-        payer = signer)]
-    pub token_pool: Account<'info, TokenAccount>,
+        constraint = proposal.dao_address == dao.key())]
+    pub proposal: Box<Account<'info, Proposal>>,
+    #[account(mut, constraint = stream.dao_address == dao.key())]
+    pub stream: Box<Account<'info, Stream>>,
     #[account(
         mut,
-        seeds = [dao.key().as_ref(), DAO_AUTH_PDA_SEED],
-        bump)]
-    pub dao_auth_pda: Account<'info, AuthAccount>,
-    #[account(mut)]
-    pub fee_receiver_address: AccountInfo<'info>,
+        seeds = [stream.key().as_ref(), TOKEN_POOL_PDA_SEED],
+        bump
+        )]
+    pub token_pool: Account<'info, TokenAccount>,
+    #[account(
+    seeds = [GLOBAL_CONFIG_PDA_SEED],
+    bump)]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
     #[account(
     mut,
     seeds = [FEE_CONTROLLER_PDA_SEED],
@@ -961,26 +2928,58 @@ pub struct InitializeStream<'info> {
 }
 
 #[derive(Accounts)]
-#[instruction()]
-pub struct ReactivateStream<'info> {
+pub struct ExecuteUpdateVerifiedCreators<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
+    #[account(mut)]
+    pub dao: Box<Account<'info, Dao>>,
     #[account(
-    mut,
-    constraint = stream.dao_address == dao.key(),
-    constraint = stream.is_active == false,
-    )]
+        mut,
+        seeds = [dao.key().as_ref(), PROPOSAL_PDA_SEED, &proposal.proposal_index.to_le_bytes()],
+        bump,
+        constraint = proposal.dao_address == dao.key())]
+    pub proposal: Box<Account<'info, Proposal>>,
+    #[account(mut, constraint = stream.dao_address == dao.key())]
     pub stream: Box<Account<'info, Stream>>,
+    #[account(
+    mut,
+    seeds = [FEE_CONTROLLER_PDA_SEED],
+    bump)]
+    pub fee_controller: Box<Account<'info, FeeController>>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteCloseStream<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
     #[account(mut)]
     pub dao: Box<Account<'info, Dao>>,
     #[account(
-    mut,
-    seeds = [stream.key().as_ref(), TOKEN_POOL_PDA_SEED],
-    bump
-    )]
+        mut,
+        seeds = [dao.key().as_ref(), PROPOSAL_PDA_SEED, &proposal.proposal_index.to_le_bytes()],
+        bump,
+        constraint = proposal.dao_address == dao.key())]
+    pub proposal: Box<Account<'info, Proposal>>,
+    #[account(mut, close = rent_receiver, constraint = stream.dao_address == dao.key())]
+    pub stream: Box<Account<'info, Stream>>,
+    #[account(
+        mut,
+        seeds = [stream.key().as_ref(), TOKEN_POOL_PDA_SEED],
+        bump
+        )]
     pub token_pool: Account<'info, TokenAccount>,
     #[account(mut)]
-    pub fee_receiver_address: AccountInfo<'info>,
+    pub receiver_token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub rent_receiver: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [dao.key().as_ref(), DAO_AUTH_PDA_SEED],
+        bump)]
+    pub dao_auth_pda: Account<'info, AuthAccount>,
     #[account(
     mut,
     seeds = [FEE_CONTROLLER_PDA_SEED],
@@ -992,13 +2991,24 @@ pub struct ReactivateStream<'info> {
 }
 
 #[derive(Accounts)]
-pub struct ProposeDaoCommand<'info> {
+pub struct InitializeTreasury<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
-    #[account(mut)]
     pub dao: Box<Account<'info, Dao>>,
-    #[account(mut)]
-    pub fee_receiver_address: AccountInfo<'info>,
+    pub token_mint: Account<'info, Mint>,
+    #[account(
+        init,
+        token::mint = token_mint,
+        token::authority = dao_auth_pda,
+        seeds = [dao.key().as_ref(), token_mint.key().as_ref(), TREASURY_PDA_SEED],
+        bump,
+        payer = signer)]
+    pub treasury: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [dao.key().as_ref(), DAO_AUTH_PDA_SEED],
+        bump)]
+    pub dao_auth_pda: Account<'info, AuthAccount>,
     #[account(
     mut,
     seeds = [FEE_CONTROLLER_PDA_SEED],
@@ -1010,13 +3020,17 @@ pub struct ProposeDaoCommand<'info> {
 }
 
 #[derive(Accounts)]
-pub struct ApproveDaoCommand<'info> {
+pub struct DepositToTreasury<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
-    #[account(mut)]
     pub dao: Box<Account<'info, Dao>>,
+    #[account(
+        mut,
+        seeds = [dao.key().as_ref(), treasury.mint.as_ref(), TREASURY_PDA_SEED],
+        bump)]
+    pub treasury: Box<Account<'info, TokenAccount>>,
     #[account(mut)]
-    pub fee_receiver_address: AccountInfo<'info>,
+    pub depositor_token_account: Account<'info, TokenAccount>,
     #[account(
     mut,
     seeds = [FEE_CONTROLLER_PDA_SEED],
@@ -1028,14 +3042,29 @@ pub struct ApproveDaoCommand<'info> {
 }
 
 #[derive(Accounts)]
-#[instruction()]
-pub struct ExecuteUpdateDaoMultisig<'info> {
+pub struct ExecuteWithdrawFromTreasury<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
     #[account(mut)]
     pub dao: Box<Account<'info, Dao>>,
+    #[account(
+        mut,
+        seeds = [dao.key().as_ref(), PROPOSAL_PDA_SEED, &proposal.proposal_index.to_le_bytes()],
+        bump,
+        constraint = proposal.dao_address == dao.key())]
+    pub proposal: Box<Account<'info, Proposal>>,
+    #[account(
+        mut,
+        seeds = [dao.key().as_ref(), treasury.mint.as_ref(), TREASURY_PDA_SEED],
+        bump)]
+    pub treasury: Box<Account<'info, TokenAccount>>,
     #[account(mut)]
-    pub fee_receiver_address: AccountInfo<'info>,
+    pub receiver_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [dao.key().as_ref(), DAO_AUTH_PDA_SEED],
+        bump)]
+    pub dao_auth_pda: Account<'info, AuthAccount>,
     #[account(
     mut,
     seeds = [FEE_CONTROLLER_PDA_SEED],
@@ -1047,21 +3076,35 @@ pub struct ExecuteUpdateDaoMultisig<'info> {
 }
 
 #[derive(Accounts)]
-pub struct ExecuteDeactivateStream<'info> {
+pub struct ExecuteFundStreamFromTreasury<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
     #[account(mut)]
     pub dao: Box<Account<'info, Dao>>,
+    #[account(
+        mut,
+        seeds = [dao.key().as_ref(), PROPOSAL_PDA_SEED, &proposal.proposal_index.to_le_bytes()],
+        bump,
+        constraint = proposal.dao_address == dao.key())]
+    pub proposal: Box<Account<'info, Proposal>>,
     #[account(mut, constraint = stream.dao_address == dao.key())]
-    pub stream: Account<'info, Stream>,
+    pub stream: Box<Account<'info, Stream>>,
     #[account(
         mut,
         seeds = [stream.key().as_ref(), TOKEN_POOL_PDA_SEED],
         bump
         )]
     pub token_pool: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub fee_receiver_address: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [dao.key().as_ref(), treasury.mint.as_ref(), TREASURY_PDA_SEED],
+        bump)]
+    pub treasury: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [dao.key().as_ref(), DAO_AUTH_PDA_SEED],
+        bump)]
+    pub dao_auth_pda: Account<'info, AuthAccount>,
     #[account(
     mut,
     seeds = [FEE_CONTROLLER_PDA_SEED],
@@ -1073,29 +3116,22 @@ pub struct ExecuteDeactivateStream<'info> {
 }
 
 #[derive(Accounts)]
-#[instruction()]
-pub struct ExecuteWithdrawFromStream<'info> {
+pub struct ExecuteCustomInstruction<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
     #[account(mut)]
     pub dao: Box<Account<'info, Dao>>,
-    #[account(mut, constraint = stream.dao_address == dao.key())]
-    pub stream: Account<'info, Stream>,
     #[account(
         mut,
-        seeds = [stream.key().as_ref(), TOKEN_POOL_PDA_SEED],
-        bump
-        )]
-    pub token_pool: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub receiver_token_account: Account<'info, TokenAccount>,
+        seeds = [dao.key().as_ref(), PROPOSAL_PDA_SEED, &proposal.proposal_index.to_le_bytes()],
+        bump,
+        constraint = proposal.dao_address == dao.key())]
+    pub proposal: Box<Account<'info, Proposal>>,
     #[account(
         mut,
         seeds = [dao.key().as_ref(), DAO_AUTH_PDA_SEED],
         bump)]
     pub dao_auth_pda: Account<'info, AuthAccount>,
-    #[account(mut)]
-    pub fee_receiver_address: AccountInfo<'info>,
     #[account(
     mut,
     seeds = [FEE_CONTROLLER_PDA_SEED],
@@ -1112,11 +3148,13 @@ pub struct ExecuteWithdrawFromStream<'info> {
 pub struct InitializeStake<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
+    #[account(mut)]
+    pub sponsor: Signer<'info>, // pays rent and fees, pass signer again when unsponsored
     #[account(
         init,
         seeds = [signer.key().as_ref(), nft_mint.key().as_ref(), STAKE_PDA_SEED],
         bump,
-        payer = signer,
+        payer = sponsor,
         space = MAX_STAKE_ACCOUNT_LEN)]
     pub stake: Account<'info, Stake>,
     pub nft_mint: Account<'info, Mint>,
@@ -1126,17 +3164,15 @@ pub struct InitializeStake<'info> {
         token::authority = stake_auth_pda,
         seeds = [stake.key().as_ref(), NFT_VAULT_PDA_SEED],
         bump,
-        payer = signer)]
+        payer = sponsor)]
     pub nft_vault: Account<'info, TokenAccount>,
     #[account(
         init,
         seeds = [stake.key().as_ref(), STAKE_AUTH_PDA_SEED],
         bump,
-        payer = signer,
+        payer = sponsor,
         space = MIN_ACCOUNT_LEN)]
     pub stake_auth_pda: Account<'info, AuthAccount>,
-    #[account(mut)]
-    pub fee_receiver_address: AccountInfo<'info>,
     #[account(
     mut,
     seeds = [FEE_CONTROLLER_PDA_SEED],
@@ -1152,6 +3188,8 @@ pub struct InitializeStake<'info> {
 pub struct StakeNft<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
+    #[account(mut)]
+    pub sponsor: Signer<'info>, // pays rent and fees, pass signer again when unsponsored
     #[account(
         mut,
         seeds = [signer.key().as_ref(), nft_mint.key().as_ref(), STAKE_PDA_SEED],
@@ -1171,8 +3209,7 @@ pub struct StakeNft<'info> {
         constraint = sender_nft_account.owner.key() == signer.key()
     )]
     pub sender_nft_account: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub fee_receiver_address: AccountInfo<'info>,
+    pub metaplex_metadata_pda: AccountInfo<'info>,
     #[account(
     mut,
     seeds = [FEE_CONTROLLER_PDA_SEED],
@@ -1214,8 +3251,6 @@ pub struct UnstakeNft<'info> {
         seeds = [stake.key().as_ref(), STAKE_AUTH_PDA_SEED],
         bump)]
     pub stake_auth_pda: Account<'info, AuthAccount>,
-    #[account(mut)]
-    pub fee_receiver_address: AccountInfo<'info>,
     #[account(
     mut,
     seeds = [FEE_CONTROLLER_PDA_SEED],
@@ -1231,11 +3266,13 @@ pub struct UnstakeNft<'info> {
 pub struct InitializeConnection<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
+    #[account(mut)]
+    pub sponsor: Signer<'info>, // pays rent and fees, pass signer again when unsponsored
     #[account(
         init,
         seeds = [stake.key().as_ref(), stream.key().as_ref(), CONNECTION_PDA_SEED],
         bump,
-        payer = signer,
+        payer = sponsor,
         space = MAX_SUBSCRIPTION_ACCOUNT_LEN)]
     pub connection: Account<'info, Connection>,
     #[account(
@@ -1248,8 +3285,6 @@ pub struct InitializeConnection<'info> {
     pub stake: Account<'info, Stake>,
     #[account(mut)]
     pub stream: Account<'info, Stream>,
-    #[account(mut)]
-    pub fee_receiver_address: AccountInfo<'info>,
     #[account(
     mut,
     seeds = [FEE_CONTROLLER_PDA_SEED],
@@ -1265,6 +3300,8 @@ pub struct InitializeConnection<'info> {
 pub struct ConnectToStream<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
+    #[account(mut)]
+    pub sponsor: Signer<'info>, // pays rent and fees, pass signer again when unsponsored
     #[account(
         mut,
         seeds = [stake.key().as_ref(), stream.key().as_ref(), CONNECTION_PDA_SEED],
@@ -1280,8 +3317,9 @@ pub struct ConnectToStream<'info> {
         constraint = stake.is_active == true,
         )]
     pub stake: Account<'info, Stake>,
-    #[account(mut)]
+    #[account(mut, constraint = stream.dao_address == dao.key())]
     pub stream: Account<'info, Stream>,
+    pub dao: Box<Account<'info, Dao>>,
     pub metaplex_metadata_pda: AccountInfo<'info>,
     #[account(
         mut,
@@ -1289,8 +3327,10 @@ pub struct ConnectToStream<'info> {
         bump
         )]
     pub token_pool: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub fee_receiver_address: AccountInfo<'info>,
+    #[account(
+    seeds = [GLOBAL_CONFIG_PDA_SEED],
+    bump)]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
     #[account(
     mut,
     seeds = [FEE_CONTROLLER_PDA_SEED],
@@ -1305,6 +3345,8 @@ pub struct ConnectToStream<'info> {
 pub struct ClaimFromStream<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
+    #[account(mut)]
+    pub sponsor: Signer<'info>, // pays rent and fees, pass signer again when unsponsored
     #[account(
     mut,
     seeds = [stake.key().as_ref(), stream.key().as_ref(), CONNECTION_PDA_SEED],
@@ -1312,7 +3354,6 @@ pub struct ClaimFromStream<'info> {
     constraint = connection.stream_address == stream.key(),
     constraint = connection.owner_address == signer.key(),
     constraint = connection.stake_address == stake.key(),
-    constraint = connection.is_active == true,
     )]
     pub connection: Box<Account<'info, Connection>>,
     #[account(
@@ -1320,27 +3361,84 @@ pub struct ClaimFromStream<'info> {
     seeds = [signer.key().as_ref(), stake.nft_mint_address.as_ref(), STAKE_PDA_SEED],
     bump,
     constraint = stake.owner_address == signer.key(),
-    constraint = stake.is_active == true,
     )]
     pub stake: Box<Account<'info, Stake>>,
     #[account(mut, constraint = stream.dao_address == dao.key())]
     pub stream: Box<Account<'info, Stream>>,
+    pub metaplex_metadata_pda: AccountInfo<'info>,
     #[account(
     mut,
     seeds = [stream.key().as_ref(), TOKEN_POOL_PDA_SEED],
     bump)]
     pub token_pool: Account<'info, TokenAccount>,
     #[account(mut)]
-    pub receiver_token_account: Account<'info, TokenAccount>,
+    pub receiver_token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub protocol_fee_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+    mut,
+    seeds = [dao.key().as_ref(), DAO_AUTH_PDA_SEED],
+    bump)]
+    pub dao_auth_pda: Account<'info, AuthAccount>,
+    #[account(mut)]
+    pub dao: Box<Account<'info, Dao>>,
+    #[account(
+    seeds = [GLOBAL_CONFIG_PDA_SEED],
+    bump)]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+    #[account(
+    mut,
+    seeds = [FEE_CONTROLLER_PDA_SEED],
+    bump)]
+    pub fee_controller: Box<Account<'info, FeeController>>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct DisconnectUnverifiedConnection<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>, // anyone, pays fees
+    #[account(
+        mut,
+        seeds = [stake.key().as_ref(), stream.key().as_ref(), CONNECTION_PDA_SEED],
+        bump,
+        constraint = connection.stream_address == stream.key(),
+        constraint = connection.stake_address == stake.key(),
+        constraint = connection.is_active == true,
+        )]
+    pub connection: Box<Account<'info, Connection>>,
+    #[account(
+        mut,
+        seeds = [connection.owner_address.as_ref(), stake.nft_mint_address.as_ref(), STAKE_PDA_SEED],
+        bump,
+        constraint = stake.owner_address == connection.owner_address,
+        )]
+    pub stake: Box<Account<'info, Stake>>,
+    #[account(mut, constraint = stream.dao_address == dao.key())]
+    pub stream: Box<Account<'info, Stream>>,
+    pub metaplex_metadata_pda: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [stream.key().as_ref(), TOKEN_POOL_PDA_SEED],
+        bump)]
+    pub token_pool: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = receiver_token_account.owner == connection.owner_address,
+        constraint = receiver_token_account.mint == stream.token_mint_address,
+        )]
+    pub receiver_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(mut)]
+    pub protocol_fee_token_account: Box<Account<'info, TokenAccount>>,
     #[account(
-    mut,
-    seeds = [dao.key().as_ref(), DAO_AUTH_PDA_SEED],
-    bump)]
+        mut,
+        seeds = [dao.key().as_ref(), DAO_AUTH_PDA_SEED],
+        bump)]
     pub dao_auth_pda: Account<'info, AuthAccount>,
     #[account(mut)]
     pub dao: Box<Account<'info, Dao>>,
-    #[account(mut)]
-    pub fee_receiver_address: AccountInfo<'info>,
     #[account(
     mut,
     seeds = [FEE_CONTROLLER_PDA_SEED],
@@ -1382,6 +3480,8 @@ pub struct DisconnectFromStream<'info> {
     pub token_pool: Account<'info, TokenAccount>,
     #[account(mut)]
     pub receiver_token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub protocol_fee_token_account: Box<Account<'info, TokenAccount>>,
     #[account(
         mut,
         seeds = [dao.key().as_ref(), DAO_AUTH_PDA_SEED],
@@ -1389,8 +3489,6 @@ pub struct DisconnectFromStream<'info> {
     pub dao_auth_pda: Account<'info, AuthAccount>,
     #[account(mut)]
     pub dao: Box<Account<'info, Dao>>,
-    #[account(mut)]
-    pub fee_receiver_address: AccountInfo<'info>,
     #[account(
     mut,
     seeds = [FEE_CONTROLLER_PDA_SEED],
@@ -1414,11 +3512,29 @@ pub struct SimulateCreateMetadata<'info> {
 }
 
 // DAO structs
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum CouncillorRole {
+    Admin = 0,
+    Treasurer = 1,
+    Operator = 2,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, FromPrimitive)]
 pub enum ProposalType {
     UpdateMultisig = 1,
     DeactivateStream = 2,
     WithdrawFromStream = 3,
+    UpdateDaoConfig = 4,
+    CustomInstruction = 5,
+    UpdateStreamRate = 6,
+    AddVerifiedCreator = 7,
+    RemoveVerifiedCreator = 8,
+    CloseStream = 9,
+    UpdateRoles = 10,
+    UnpauseDao = 11,
+    WithdrawFromTreasury = 12,
+    FundStreamFromTreasury = 13,
+    UpdateDaoMetadata = 14,
 }
 
 impl Default for ProposalType {
@@ -1427,16 +3543,46 @@ impl Default for ProposalType {
     }
 }
 
-#[account]
-#[derive(Default)]
-pub struct Dao {
-    // config
-    pub councillors: Vec<Pubkey>,
-    pub approval_threshold: u64,
-    // proposal state
-    pub proposal_signers: Vec<bool>,
-    pub proposal_is_active: bool,
-    pub proposal_type: ProposalType, // 4 bytes
+impl ProposalType {
+    // permission a councillor's role needs to propose or approve this type
+    pub fn vote_permission(&self) -> u16 {
+        match self {
+            ProposalType::UpdateMultisig
+            | ProposalType::UpdateDaoConfig
+            | ProposalType::CustomInstruction
+            | ProposalType::UpdateRoles
+            | ProposalType::UnpauseDao
+            | ProposalType::UpdateDaoMetadata => PERMISSION_VOTE_GOVERNANCE,
+            ProposalType::WithdrawFromStream
+            | ProposalType::CloseStream
+            | ProposalType::WithdrawFromTreasury
+            | ProposalType::FundStreamFromTreasury => PERMISSION_VOTE_TREASURY,
+            ProposalType::DeactivateStream
+            | ProposalType::UpdateStreamRate
+            | ProposalType::AddVerifiedCreator
+            | ProposalType::RemoveVerifiedCreator => PERMISSION_VOTE_STREAMS,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Default)]
+pub enum ProposalStatus {
+    #[default]
+    Active,
+    Executed,
+    Expired,
+    Cancelled,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct ProposalAccountMeta {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct ProposalParams {
     // update multisig proposal params
     pub proposed_councillors: Vec<Pubkey>,
     pub proposed_approval_threshold: u64,
@@ -1446,8 +3592,175 @@ pub struct Dao {
     pub proposed_withdrawal_amount: u64,
     pub proposed_withdrawal_receiver_owner: Pubkey,
     pub proposed_withdrawal_stream: Pubkey,
+    // update dao config proposal params
+    pub proposed_voting_window_seconds: u64,
+    pub proposed_execution_delay_seconds: u64,
+    pub proposed_guardian: Pubkey,
+    // custom instruction proposal params, invoked with dao_auth_pda as signer
+    pub proposed_instruction_program_id: Pubkey,
+    pub proposed_instruction_accounts: Vec<ProposalAccountMeta>,
+    pub proposed_instruction_data: Vec<u8>,
+    // stream proposal params
+    pub proposed_stream: Pubkey,
+    pub proposed_stream_rate: u64,
+    pub proposed_creator_address: Pubkey,
+    pub proposed_rent_receiver: Pubkey,
+    pub proposed_treasury_mint: Pubkey,
+    pub proposed_metadata: DaoMetadata,
+    // role proposal params
+    pub proposed_councillor_roles: Vec<CouncillorRole>,
+    pub proposed_role_permissions: Vec<u16>,
+    pub proposed_role_approval_thresholds: Vec<u64>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct DaoMetadata {
+    pub display_name: String, // mutable, unlike the name that seeds the dao address
+    pub image_uri: String,
+    pub website_uri: String,
+    pub description_hash: [u8; 32], // sha256 of the off-chain description
+}
+
+// initial councillors, roles and proposal timing passed to initialize_dao
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct DaoGovernanceConfig {
+    pub councillors: Vec<Pubkey>,
+    pub councillor_roles: Vec<CouncillorRole>, // parallel to councillors
+    pub approval_threshold: u64,
+    pub proposal_voting_window_seconds: u64, // zero disables expiry
+    pub execution_delay_seconds: u64,
+    pub guardian: Pubkey, // default pubkey leaves pausing to councillors only
+}
+
+#[account]
+#[derive(Default)]
+pub struct Dao {
+    // identity
+    pub creator: Pubkey,
+    pub name: String, // seeds the dao address with creator, see find_dao_address. fixed, metadata holds the display name
+    pub metadata: DaoMetadata,
+    // config
+    pub councillors: Vec<Pubkey>,
+    pub approval_threshold: u64,
+    pub councillor_roles: Vec<CouncillorRole>, // parallel to councillors
+    pub role_permissions: Vec<u16>, // permission bitmap per role
+    pub role_approval_thresholds: Vec<u64>, // approvals needed per role on proposals it can vote on, zero disables
+    // proposal state
+    pub multisig_nonce: u64, // bumped whenever councillors change
+    pub num_proposals: u64,
+    pub proposal_voting_window_seconds: u64, // zero disables expiry
+    pub execution_delay_seconds: u64, // wait between reaching threshold and execution
+    // emergency state
+    pub guardian: Pubkey, // may pause alongside any councillor, default pubkey disables
+    pub paused: bool, // blocks connects, claims, withdrawals and new streams
     // stream state
-    pub num_streams: u64,
+    pub num_streams: u64, // streams are indexed 0..num_streams, closed ones leave gaps
+}
+
+impl Dao {
+    pub fn councillor_index_with_permission(&self, pubkey: &Pubkey, permission: u16) -> core::result::Result<usize, ProgramError> {
+        let councillor_index = self.councillors
+            .iter()
+            .position(|a| a == pubkey)
+            .ok_or(ErrorCode::InvalidCouncillor)?;
+        let role = self.councillor_roles[councillor_index];
+        if self.role_permissions[role as usize] & permission != permission {
+            return Err(ErrorCode::MissingRolePermission.into());
+        }
+        Ok(councillor_index)
+    }
+
+    // overall threshold plus the threshold of every role allowed to vote on the proposal
+    pub fn approval_threshold_met(&self, proposal: &Proposal) -> bool {
+        if proposal.num_approvals() < self.approval_threshold {
+            return false;
+        }
+        let permission = proposal.proposal_type.vote_permission();
+        for (role_index, role_threshold) in self.role_approval_thresholds.iter().enumerate() {
+            if *role_threshold == 0 || self.role_permissions[role_index] & permission == 0 {
+                continue;
+            }
+            let role_approvals = proposal.proposal_signers
+                .iter()
+                .zip(self.councillor_roles.iter())
+                .filter(|(signed, role)| **signed && **role as usize == role_index)
+                .count() as u64;
+            if role_approvals < *role_threshold {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[account]
+#[derive(Default)]
+pub struct Proposal {
+    // config
+    pub dao_address: Pubkey,
+    pub proposal_index: u64,
+    pub proposer: Pubkey,
+    pub proposal_type: ProposalType,
+    pub params: ProposalParams,
+    pub content_hash: [u8; 32], // sha256 of proposal type and params, see proposal_content_hash
+    pub multisig_nonce: u64, // dao multisig_nonce the signers refer to
+    pub created_timestamp: u64,
+    pub voting_deadline_timestamp: u64, // zero never expires
+    // state
+    pub proposal_signers: Vec<bool>,
+    pub status: ProposalStatus,
+    pub threshold_reached_timestamp: u64, // zero until enough councillors approve
+}
+
+impl Proposal {
+    pub fn is_expired(&self, current_timestamp: u64) -> bool {
+        self.voting_deadline_timestamp > 0 && current_timestamp > self.voting_deadline_timestamp
+    }
+
+    // an approval stays executable for one voting window after the timelock elapses
+    pub fn execution_deadline_timestamp(&self, dao: &Dao) -> u64 {
+        if self.voting_deadline_timestamp == 0 || self.threshold_reached_timestamp == 0 {
+            return 0;
+        }
+        self.threshold_reached_timestamp + dao.execution_delay_seconds + dao.proposal_voting_window_seconds
+    }
+
+    pub fn is_execution_expired(&self, dao: &Dao, current_timestamp: u64) -> bool {
+        let execution_deadline_timestamp = self.execution_deadline_timestamp(dao);
+        execution_deadline_timestamp > 0 && current_timestamp > execution_deadline_timestamp
+    }
+
+    pub fn num_approvals(&self) -> u64 {
+        self.proposal_signers.iter().filter(|s| **s).count() as u64
+    }
+
+    // starts the execution timelock the first time the threshold is met
+    pub fn update_threshold_reached(&mut self, dao: &Dao, current_timestamp: u64) {
+        if self.threshold_reached_timestamp == 0 && dao.approval_threshold_met(self) {
+            self.threshold_reached_timestamp = current_timestamp;
+        }
+    }
+}
+
+#[event]
+pub struct ApprovalRevoked {
+    pub dao_address: Pubkey,
+    pub proposal_address: Pubkey,
+    pub councillor: Pubkey,
+    pub num_approvals: u64,
+}
+
+#[event]
+pub struct ProposalCancelled {
+    pub dao_address: Pubkey,
+    pub proposal_address: Pubkey,
+    pub cancelled_by: Pubkey,
+}
+
+#[event]
+pub struct DaoPaused {
+    pub dao_address: Pubkey,
+    pub paused_by: Pubkey,
 }
 
 #[account]
@@ -1455,6 +3768,7 @@ pub struct Dao {
 pub struct Stream {
     // config
     pub dao_address: Pubkey,
+    pub stream_index: u64, // see find_stream_address
     pub token_mint_address: Pubkey,
     pub token_pool_address: Pubkey,
     pub verified_creator_addresses: Vec<Pubkey>,
@@ -1465,6 +3779,7 @@ pub struct Stream {
     pub num_connections: u64,
     pub total_streamed: u64,
     pub total_claimed: u64,
+    pub earned_per_connection: u64, // cumulative amount a connection active since creation would have earned
     pub last_update_timestamp: u64,
 }
 
@@ -1495,28 +3810,171 @@ pub struct Connection {
     // state
     pub total_earned: u64,
     pub total_claimed: u64,
+    pub earned_per_connection_snapshot: u64, // stream earned_per_connection at last update
     pub last_update_timestamp: u64,
     pub is_active: bool,
 }
 
+// Global config structs
+#[account]
+#[derive(Default)]
+pub struct GlobalConfig {
+    pub max_decimals: u8,
+    pub max_stream_rate: u64,
+    pub max_connections_per_stream: u64,
+    pub is_paused: bool, // blocks new streams, connects and claims across all daos
+}
+
+// Fee structs
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, FromPrimitive)]
+pub enum FeeInstruction {
+    InitializeDao = 0,
+    InitializeStream = 1,
+    ReactivateStream = 2,
+    ProposeDaoCommand = 3,
+    ApproveDaoCommand = 4,
+    ExecuteUpdateDaoMultisig = 5,
+    ExecuteDeactivateStream = 6,
+    ExecuteWithdrawFromStream = 7,
+    InitializeStake = 8,
+    StakeNft = 9,
+    UnstakeNft = 10,
+    InitializeConnection = 11,
+    ConnectToStream = 12,
+    ClaimFromStream = 13,
+    DisconnectFromStream = 14,
+    ExecuteUpdateDaoConfig = 15,
+    RevokeApproval = 16,
+    CancelProposal = 17,
+    ExecuteCustomInstruction = 18,
+    ExecuteUpdateStreamRate = 19,
+    ExecuteUpdateVerifiedCreators = 20,
+    DisconnectUnverifiedConnection = 21,
+    ExecuteCloseStream = 22,
+    ExecuteUpdateDaoRoles = 23,
+    PauseDao = 24,
+    ExecuteUnpauseDao = 25,
+    InitializeTreasury = 26,
+    DepositToTreasury = 27,
+    ExecuteWithdrawFromTreasury = 28,
+    ExecuteFundStreamFromTreasury = 29,
+    ExecuteUpdateDaoMetadata = 30,
+}
+
+// fee schedules must cover every variant, keep this pointing at the last one
+// MAX_INSTRUCTIONS stays the storage capacity of the fee vectors
+pub const NUM_FEE_INSTRUCTIONS: usize = FeeInstruction::ExecuteUpdateDaoMetadata as usize + 1;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct FeeReceiver {
+    pub address: Pubkey,
+    pub share_bps: u16,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct FeeExemption {
+    pub creator_address: Pubkey,
+    pub discount_bps: u16,
+}
+
 #[account]
 #[derive(Default)]
 pub struct FeeController {
-    pub initialize_dao: u64,
-    pub initialize_stream: u64,
-    pub reactivate_stream: u64,
-    pub propose_dao_command: u64,
-    pub approve_dao_command: u64,
-    pub execute_update_dao_multisig: u64,
-    pub execute_deactivate_stream: u64,
-    pub execute_withdraw_from_stream: u64,
-    pub initialize_stake: u64,
-    pub stake_nft: u64,
-    pub unstake_nft: u64,
-    pub initialize_connection: u64,
-    pub connect_to_stream: u64,
-    pub claim_from_stream: u64,
-    pub disconnect_from_stream: u64,
+    // authority (m of n, any co-signers are passed as remaining accounts)
+    pub fee_authorities: Vec<Pubkey>,
+    pub fee_approval_threshold: u64,
+    pub pending_fee_authorities: Vec<Pubkey>,
+    pub pending_fee_approval_threshold: u64,
+    // receivers (passed in this order as remaining accounts to fee-charging instructions)
+    pub fee_receivers: Vec<FeeReceiver>,
+    // fees
+    pub fees: Vec<u64>, // lamports, indexed by FeeInstruction
+    // fee increases are staged here until the delay has passed
+    pub fee_increase_delay_seconds: u64,
+    pub pending_fees: Vec<u64>, // empty when nothing is pending
+    pub pending_token_fees: Vec<u64>, // empty when nothing is pending
+    pub pending_fees_effective_timestamp: u64,
+    pub pending_fee_increase_delay_seconds: Option<u64>, // shortening the delay is staged too
+    pub pending_claim_fee_bps: Option<u16>,
+    pub pending_fee_mint: Option<Option<Pubkey>>, // None when no mint change is pending
+    // optional spl-token fees, charged instead of lamports when a mint is set
+    pub fee_mint: Option<Pubkey>,
+    pub token_fees: Vec<u64>, // absolute token units, indexed by FeeInstruction
+    // accounting since fee_stats_reset_timestamp, indexed by FeeInstruction
+    pub fees_collected: Vec<u64>,
+    pub token_fees_collected: Vec<u64>,
+    pub fee_tx_count: Vec<u64>,
+    pub fee_stats_reset_timestamp: u64,
+    // discounts for holders of nfts with a matching verified creator
+    pub fee_exemptions: Vec<FeeExemption>,
+    // protocol fee on claimed tokens, paid to a token account owned by protocol_fee_owner
+    pub claim_fee_bps: u16,
+    pub protocol_fee_owner: Pubkey,
+}
+
+impl FeeController {
+    pub fn fee(&self, fee_instruction: FeeInstruction) -> u64 {
+        self.fees.get(fee_instruction as usize).copied().unwrap_or(0)
+    }
+
+    pub fn token_fee(&self, fee_instruction: FeeInstruction) -> u64 {
+        self.token_fees.get(fee_instruction as usize).copied().unwrap_or(0)
+    }
+
+    pub fn record_fee(&mut self, fee_instruction: FeeInstruction, fee_lamports: u64, fee_tokens: u64) {
+        let index = fee_instruction as usize;
+        for counter in [&mut self.fees_collected, &mut self.token_fees_collected, &mut self.fee_tx_count] {
+            if counter.len() <= index {
+                counter.resize(MAX_INSTRUCTIONS, 0);
+            }
+        }
+        self.fees_collected[index] = self.fees_collected[index].saturating_add(fee_lamports);
+        self.token_fees_collected[index] = self.token_fees_collected[index].saturating_add(fee_tokens);
+        self.fee_tx_count[index] = self.fee_tx_count[index].saturating_add(1);
+    }
+}
+
+#[account]
+#[derive(Default)]
+pub struct FeeOverride {
+    pub dao_address: Pubkey,
+    pub fee_bps: Vec<u32>, // multiplier on the global fee, indexed by FeeInstruction
+    pub pending_fee_bps: Vec<u32>, // empty when nothing is pending
+    pub pending_fees_effective_timestamp: u64,
+}
+
+impl FeeOverride {
+    // pending increases take effect lazily once due, no separate apply step needed
+    pub fn fee_bps(&self, fee_instruction: FeeInstruction, current_timestamp: u64) -> u32 {
+        let fee_bps = if self.is_pending_due(current_timestamp) {
+            &self.pending_fee_bps
+        } else {
+            &self.fee_bps
+        };
+        fee_bps.get(fee_instruction as usize).copied().unwrap_or(BPS_DENOMINATOR as u32)
+    }
+
+    // returns whether an increase was staged
+    pub fn set_fee_bps(&mut self, fee_instruction: FeeInstruction, fee_bps: u32, current_timestamp: u64) -> bool {
+        if self.is_pending_due(current_timestamp) {
+            self.fee_bps = self.pending_fee_bps.clone();
+            self.pending_fee_bps = Vec::new();
+        }
+        self.fee_bps.resize(MAX_INSTRUCTIONS.max(self.fee_bps.len()), BPS_DENOMINATOR as u32);
+
+        // build on any increases already pending so consecutive updates accumulate
+        let mut new_fee_bps = if self.pending_fee_bps.is_empty() {
+            self.fee_bps.clone()
+        } else {
+            self.pending_fee_bps.clone()
+        };
+        new_fee_bps[fee_instruction as usize] = fee_bps;
+        stage_fee_schedule(&mut self.fee_bps, &mut self.pending_fee_bps, new_fee_bps)
+    }
+
+    fn is_pending_due(&self, current_timestamp: u64) -> bool {
+        !self.pending_fee_bps.is_empty() && current_timestamp >= self.pending_fees_effective_timestamp
+    }
 }
 
 #[account]
@@ -1524,6 +3982,16 @@ pub struct FeeController {
 pub struct AuthAccount {}
 
 // utils
+// lets clients resolve a dao from its creator and name
+pub fn find_dao_address(creator: &Pubkey, name: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[creator.as_ref(), DAO_PDA_SEED, name.as_bytes()], &ID)
+}
+
+// lets clients enumerate a dao's streams without scanning program accounts
+pub fn find_stream_address(dao_address: &Pubkey, stream_index: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[dao_address.as_ref(), STREAM_PDA_SEED, &stream_index.to_le_bytes()], &ID)
+}
+
 fn update_stream_state(stream: &mut Account<Stream>,
                            current_pool_balance: u64,
                            current_timestamp: u64,
@@ -1555,6 +4023,7 @@ fn update_stream_state(stream: &mut Account<Stream>,
         msg!("Got current_surplus: {:?} and new_reduced_total_streamed: {:?}", current_pool_surplus, new_reduced_total_streamed);
 
         stream.total_streamed += new_reduced_total_streamed;
+        stream.earned_per_connection += stream.stream_rate * runway_since_last_update_seconds;
         stream.last_update_timestamp += runway_since_last_update_seconds;
         stream.is_active = false;
 
@@ -1562,6 +4031,7 @@ fn update_stream_state(stream: &mut Account<Stream>,
 
         // proceed with update
         stream.total_streamed = new_total_streamed as u64;
+        stream.earned_per_connection += stream.stream_rate * seconds_since_last_update;
         stream.last_update_timestamp = current_timestamp;
     }
 
@@ -1572,6 +4042,95 @@ fn update_stream_state(stream: &mut Account<Stream>,
     Ok(())
 }
 
+// accrues at whatever rates applied since the last snapshot, so rate changes never rewrite past earnings
+fn update_connection_state(stream: &Stream, connection: &mut Connection) {
+    let recently_earned = stream.earned_per_connection - connection.earned_per_connection_snapshot;
+    msg!("calculated recently_earned: {:?}", recently_earned);
+    connection.total_earned += recently_earned;
+    connection.earned_per_connection_snapshot = stream.earned_per_connection;
+    connection.last_update_timestamp = stream.last_update_timestamp;
+}
+
+// checks the staked nft carries a verified creator listed on the stream
+fn nft_has_verified_creator(stream: &Stream, nft_mint: &Pubkey, metaplex_metadata_info: &AccountInfo) -> core::result::Result<bool, ProgramError> {
+
+    // manually lookup metaplex metadata pda and ensure match with address
+    let metadata_program_id: Pubkey = if stream.is_simulation {
+        ID
+    } else {
+        spl_token_metadata::ID
+    };
+    let metadata_seeds = &[
+        METADATA_PREFIX,
+        metadata_program_id.as_ref(),
+        nft_mint.as_ref(),
+    ];
+    let (metaplex_metadata_pda, _bump) = Pubkey::find_program_address(metadata_seeds, &metadata_program_id);
+    if metaplex_metadata_pda != metaplex_metadata_info.key() {
+        return Err(ErrorCode::InvalidMetaplexMetadataPda.into());
+    }
+
+    // load creator vec from metadata and cross check it for any matches with stream verified addresses
+    let metadata = deser_metadata(metaplex_metadata_info, stream.is_simulation)?;
+    // metadata without creators can't match any verified address
+    let creators_vec = match metadata.data.creators.as_ref() {
+        Some(creators_vec) => creators_vec,
+        None => return Ok(false),
+    };
+    let found_match = stream.verified_creator_addresses
+        .iter()
+        .any(|creator_pubkey| creators_vec.iter().any(|c| (c.address == *creator_pubkey) && c.verified));
+
+    Ok(found_match)
+}
+
+// pays out of the token pool, splitting off the protocol fee
+fn transfer_from_token_pool<'a>(
+    token_pool: &Account<'a, TokenAccount>,
+    receiver_token_account: &Account<'a, TokenAccount>,
+    protocol_fee_token_account: &Account<'a, TokenAccount>,
+    dao_auth_pda: &Account<'a, AuthAccount>,
+    token_program: &Program<'a, Token>,
+    fee_controller: &FeeController,
+    signer: &[&[&[u8]]],
+    amount: u64,
+) -> ProgramResult {
+
+    let protocol_fee_amount = apply_bps(amount, fee_controller.claim_fee_bps as u64);
+    let amount_to_receiver = amount - protocol_fee_amount;
+    msg!("protocol fee amount: {:?}", protocol_fee_amount);
+
+    // transfer
+    let cpi_accounts = Transfer {
+        from: token_pool.to_account_info(),
+        to: receiver_token_account.to_account_info(),
+        authority: dao_auth_pda.to_account_info(),
+    };
+    let cpi_program = token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+    token::transfer(cpi_ctx, amount_to_receiver)?;
+
+    // transfer protocol fee
+    if protocol_fee_amount > 0 {
+        if protocol_fee_token_account.owner != fee_controller.protocol_fee_owner {
+            return Err(ErrorCode::InvalidProtocolFeeAccount.into());
+        }
+        if protocol_fee_token_account.mint != token_pool.mint {
+            return Err(ErrorCode::InvalidProtocolFeeAccount.into());
+        }
+        let cpi_accounts = Transfer {
+            from: token_pool.to_account_info(),
+            to: protocol_fee_token_account.to_account_info(),
+            authority: dao_auth_pda.to_account_info(),
+        };
+        let cpi_program = token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, protocol_fee_amount)?;
+    }
+
+    Ok(())
+}
+
 pub fn check_owner(info: &AccountInfo, is_simulation: bool) -> ProgramResult {
     let actual_owner = *info.owner;
     let expected_owner = if is_simulation {
@@ -1595,28 +4154,232 @@ pub fn deser_metadata(info: &AccountInfo, is_simulation: bool) -> core::result::
     Ok(md)
 }
 
-pub fn validate_proposal_approval(dao: &mut Account<Dao>, signer_pubkey: &Pubkey) -> ProgramResult {
+pub fn validate_proposal_is_current(dao: &Dao, proposal: &Proposal) -> ProgramResult {
+    if proposal.status != ProposalStatus::Active {
+        return Err(ErrorCode::ProposalNotActive.into());
+    }
+    if proposal.multisig_nonce != dao.multisig_nonce {
+        return Err(ErrorCode::StaleProposal.into());
+    }
+    let current_timestamp = Clock::get().unwrap().unix_timestamp as u64;
+    if proposal.is_expired(current_timestamp) {
+        return Err(ErrorCode::ProposalExpired.into());
+    }
+    Ok(())
+}
+
+pub fn validate_proposal_approval(dao: &Dao, proposal: &Proposal, signer_pubkey: &Pubkey) -> ProgramResult {
     let _councillor_index = dao.councillors
         .iter()
         .position(|a| a == signer_pubkey)
         .ok_or(ErrorCode::InvalidCouncillor)?;
-    if !dao.proposal_is_active {
+    if proposal.status != ProposalStatus::Active {
         return Err(ErrorCode::ProposalNotActive.into());
     }
-    // calculate total signers and ensure meets threshold
-    let mut num_signers = 0;
-    for i in 0..dao.councillors.len() {
-        if dao.proposal_signers[i] {
-            num_signers += 1;
+    if proposal.multisig_nonce != dao.multisig_nonce {
+        return Err(ErrorCode::StaleProposal.into());
+    }
+    // calculate total signers and ensure meets threshold, a proposal that reached threshold
+    // before the voting deadline stays executable until its execution deadline
+    let current_timestamp = Clock::get().unwrap().unix_timestamp as u64;
+    if !dao.approval_threshold_met(proposal) || proposal.threshold_reached_timestamp == 0 {
+        if proposal.is_expired(current_timestamp) {
+            return Err(ErrorCode::ProposalExpired.into());
+        }
+        return Err(ErrorCode::NotEnoughSignersApproved.into());
+    }
+    // ensure the execution timelock has elapsed
+    if current_timestamp < proposal.threshold_reached_timestamp + dao.execution_delay_seconds {
+        return Err(ErrorCode::ExecutionTimelockActive.into());
+    }
+    if proposal.is_execution_expired(dao, current_timestamp) {
+        return Err(ErrorCode::ExecutionWindowClosed.into());
+    }
+    Ok(())
+}
+
+pub fn validate_governance_config(proposal_voting_window_seconds: u64, execution_delay_seconds: u64) -> ProgramResult {
+    // expiring proposals need a timelock no longer than the window an approval stays executable for
+    if proposal_voting_window_seconds > 0 && execution_delay_seconds > proposal_voting_window_seconds {
+        return Err(ErrorCode::InvalidGovernanceConfig.into());
+    }
+    Ok(())
+}
+
+pub fn proposal_content_hash(proposal_type: ProposalType, params: &ProposalParams) -> core::result::Result<[u8; 32], ProgramError> {
+    let params_data = params.try_to_vec()?;
+    Ok(hashv(&[&[proposal_type as u8], &params_data]).to_bytes())
+}
+
+pub fn validate_protocol_not_paused(global_config: &GlobalConfig) -> ProgramResult {
+    if global_config.is_paused {
+        return Err(ErrorCode::ProtocolPaused.into());
+    }
+    Ok(())
+}
+
+pub fn validate_dao_name(name: &str) -> ProgramResult {
+    // the name is used as a pda seed, which caps its length
+    if name.is_empty() || name.len() > MAX_DAO_NAME_LEN {
+        return Err(ErrorCode::InvalidDaoName.into());
+    }
+    Ok(())
+}
+
+pub fn validate_dao_metadata(metadata: &DaoMetadata) -> ProgramResult {
+    if metadata.display_name.is_empty() || metadata.display_name.len() > MAX_DAO_NAME_LEN {
+        return Err(ErrorCode::InvalidDaoMetadata.into());
+    }
+    if metadata.image_uri.len() > MAX_DAO_URI_LEN || metadata.website_uri.len() > MAX_DAO_URI_LEN {
+        return Err(ErrorCode::InvalidDaoMetadata.into());
+    }
+    Ok(())
+}
+
+pub fn validate_dao_not_paused(dao: &Dao) -> ProgramResult {
+    if dao.paused {
+        return Err(ErrorCode::DaoPaused.into());
+    }
+    Ok(())
+}
+
+pub fn default_role_permissions() -> Vec<u16> {
+    let mut role_permissions = vec![0; NUM_COUNCILLOR_ROLES];
+    role_permissions[CouncillorRole::Admin as usize] = ALL_PERMISSIONS;
+    role_permissions[CouncillorRole::Treasurer as usize] = PERMISSION_PROPOSE | PERMISSION_VOTE_TREASURY;
+    role_permissions[CouncillorRole::Operator as usize] = PERMISSION_INITIALIZE_STREAM | PERMISSION_REACTIVATE_STREAM;
+    role_permissions
+}
+
+pub fn validate_role_config(
+    councillors: &[Pubkey],
+    councillor_roles: &[CouncillorRole],
+    approval_threshold: u64,
+    role_permissions: &[u16],
+    role_approval_thresholds: &[u64],
+) -> ProgramResult {
+    if councillor_roles.len() != councillors.len()
+        || role_permissions.len() != NUM_COUNCILLOR_ROLES
+        || role_approval_thresholds.len() != NUM_COUNCILLOR_ROLES {
+        return Err(ErrorCode::InvalidRoleConfig.into());
+    }
+    if role_permissions.iter().any(|p| p & !ALL_PERMISSIONS != 0) {
+        return Err(ErrorCode::InvalidRoleConfig.into());
+    }
+    // every role threshold must be reachable by the councillors holding that role
+    for (role_index, role_threshold) in role_approval_thresholds.iter().enumerate() {
+        let role_count = councillor_roles.iter().filter(|r| **r as usize == role_index).count() as u64;
+        if *role_threshold > role_count {
+            return Err(ErrorCode::InvalidRoleConfig.into());
+        }
+    }
+    // enough governance voters must remain to change the configuration again
+    let governance_voters = councillor_roles
+        .iter()
+        .filter(|r| role_permissions[**r as usize] & (PERMISSION_PROPOSE | PERMISSION_VOTE_GOVERNANCE) == (PERMISSION_PROPOSE | PERMISSION_VOTE_GOVERNANCE))
+        .count() as u64;
+    if governance_voters < approval_threshold {
+        return Err(ErrorCode::InvalidRoleConfig.into());
+    }
+    Ok(())
+}
+
+pub fn validate_authority_config(authorities: &[Pubkey], approval_threshold: u64) -> ProgramResult {
+    if authorities.len() > MAX_NUM_COUNCILLORS || authorities.is_empty() {
+        return Err(ErrorCode::TooManyManagers.into());
+    }
+    if (approval_threshold as usize > authorities.len()) || (approval_threshold < 1) {
+        return Err(ErrorCode::InvalidApprovalThreshold.into());
+    }
+    Ok(())
+}
+
+pub fn validate_authority_signers(authorities: &[Pubkey],
+                                  approval_threshold: u64,
+                                  signer: &Signer,
+                                  co_signers: &[AccountInfo]) -> ProgramResult {
+    let _authority_index = authorities
+        .iter()
+        .position(|a| a == signer.key)
+        .ok_or(ErrorCode::InvalidFeeAuthority)?;
+
+    // mark every authority that signed this transaction, duplicates count once
+    let mut signers = Vec::new();
+    signers.resize(authorities.len(), false);
+    let signer_keys = co_signers
+        .iter()
+        .filter(|a| a.is_signer)
+        .map(|a| a.key)
+        .chain(std::iter::once(signer.key));
+    for signer_key in signer_keys {
+        if let Some(i) = authorities.iter().position(|a| a == signer_key) {
+            signers[i] = true;
         }
     }
-    if num_signers < dao.approval_threshold {
+    let num_signers = signers.iter().filter(|s| **s).count() as u64;
+    if num_signers < approval_threshold {
         return Err(ErrorCode::NotEnoughSignersApproved.into());
     }
     Ok(())
 }
 
-pub fn transfer_fee<'a>(signer: &Signer<'a>, fee_rx_acct_info: &AccountInfo<'a>, fee_lamports: u64) -> ProgramResult {
+pub fn transfer_fee<'a>(signer: &Signer<'a>,
+                        fee_controller: &mut FeeController,
+                        fee_acct_infos: &[AccountInfo<'a>],
+                        token_program: &Program<'a, Token>,
+                        fee_instruction: FeeInstruction,
+                        fee_dao_address: Option<Pubkey>,
+                        fee_discount_bps: u16) -> ProgramResult {
+
+    // fee accounts lead the remaining accounts: the receivers in order, or [payer token account, fee vault] when paying in tokens
+    let num_fee_accounts = match fee_controller.fee_mint {
+        Some(_) => 2,
+        None => fee_controller.fee_receivers.len(),
+    };
+    if fee_acct_infos.len() < num_fee_accounts {
+        return Err(ErrorCode::InvalidFeeRxAddress.into());
+    }
+
+    // dao instructions must pass the fee override pda right after the fee accounts, so a surcharge can't be dodged by omitting it
+    let mut fee_bps = BPS_DENOMINATOR as u32;
+    if let Some(fee_dao_address) = fee_dao_address {
+        let fee_override_info = fee_acct_infos.get(num_fee_accounts).ok_or(ErrorCode::InvalidFeeOverride)?;
+        let (fee_override_pda, _bump) = Pubkey::find_program_address(&[fee_dao_address.as_ref(), FEE_OVERRIDE_PDA_SEED], &ID);
+        if fee_override_pda != fee_override_info.key() {
+            return Err(ErrorCode::InvalidFeeOverride.into());
+        }
+
+        // an uninitialized pda means no override, the global fee applies
+        if !fee_override_info.data_is_empty() {
+            let fee_override: Account<FeeOverride> = Account::try_from(fee_override_info)?;
+            let current_timestamp = Clock::get().unwrap().unix_timestamp as u64;
+            fee_bps = fee_override.fee_bps(fee_instruction, current_timestamp);
+        }
+    }
+
+    // apply any holder discount on top of the override
+    let fee_bps = apply_bps(fee_bps as u64, BPS_DENOMINATOR - fee_discount_bps.min(BPS_DENOMINATOR as u16) as u64);
+
+    match fee_controller.fee_mint {
+        Some(fee_mint) => {
+            let fee_amount = apply_bps(fee_controller.token_fee(fee_instruction), fee_bps);
+            transfer_token_fee(signer, &fee_acct_infos[..num_fee_accounts], token_program, &fee_mint, fee_amount)?;
+            fee_controller.record_fee(fee_instruction, 0, fee_amount);
+        },
+        None => {
+            let fee_lamports = apply_bps(fee_controller.fee(fee_instruction), fee_bps);
+            transfer_lamport_fee(signer, fee_controller, &fee_acct_infos[..num_fee_accounts], fee_lamports)?;
+            fee_controller.record_fee(fee_instruction, fee_lamports, 0);
+        }
+    }
+
+    Ok(())
+}
+
+fn transfer_lamport_fee<'a>(signer: &Signer<'a>,
+                            fee_controller: &FeeController,
+                            fee_rx_acct_infos: &[AccountInfo<'a>],
+                            fee_lamports: u64) -> ProgramResult {
 
     // check sufficient balance to pay initialization fee
     let user_balance = signer.lamports();
@@ -1624,23 +4387,121 @@ pub fn transfer_fee<'a>(signer: &Signer<'a>, fee_rx_acct_info: &AccountInfo<'a>,
         return Err(ErrorCode::InsufficientFeeFunds.into());
     }
 
-    // validate fee receiver ata
-    let fee_rx_address: Pubkey = Pubkey::from_str(FEE_RX_ADDRESS).unwrap();
-    if fee_rx_acct_info.key() != fee_rx_address {
-    return Err(ErrorCode::InvalidFeeRxAddress.into());
+    // validate fee receivers
+    let fee_receivers = &fee_controller.fee_receivers;
+    for (fee_receiver, fee_rx_acct_info) in fee_receivers.iter().zip(fee_rx_acct_infos.iter()) {
+        if fee_rx_acct_info.key() != fee_receiver.address {
+            return Err(ErrorCode::InvalidFeeRxAddress.into());
+        }
+    }
+
+    // split by share, the last receiver picks up any rounding dust
+    let mut remaining_lamports = fee_lamports;
+    for (i, fee_rx_acct_info) in fee_rx_acct_infos.iter().enumerate() {
+        let share_lamports = if i == fee_receivers.len() - 1 {
+            remaining_lamports
+        } else {
+            apply_bps(fee_lamports, fee_receivers[i].share_bps as u64)
+        };
+        remaining_lamports -= share_lamports;
+        if share_lamports == 0 {
+            continue;
+        }
+
+        let ix = transfer(signer.key, fee_rx_acct_info.key, share_lamports);
+
+        invoke(&ix,
+               &[
+                   signer.to_account_info(),
+                   fee_rx_acct_info.to_account_info(),
+               ])?;
     }
 
-    let ix = transfer(signer.key, fee_rx_acct_info.key, fee_lamports);
+    Ok(())
+}
+
+fn transfer_token_fee<'a>(signer: &Signer<'a>,
+                          fee_acct_infos: &[AccountInfo<'a>],
+                          token_program: &Program<'a, Token>,
+                          fee_mint: &Pubkey,
+                          fee_amount: u64) -> ProgramResult {
+
+    // validate fee vault, the token program enforces the payer account mint and balance
+    let payer_token_account = &fee_acct_infos[0];
+    let fee_vault = &fee_acct_infos[1];
+    let (fee_vault_pda, _bump) = Pubkey::find_program_address(&[fee_mint.as_ref(), FEE_VAULT_PDA_SEED], &ID);
+    if fee_vault_pda != fee_vault.key() {
+        return Err(ErrorCode::InvalidFeeVault.into());
+    }
+    if fee_amount == 0 {
+        return Ok(());
+    }
 
-    invoke(&ix,
-           &[
-               signer.to_account_info(),
-               fee_rx_acct_info.to_account_info(),
-           ])?;
+    let cpi_accounts = Transfer {
+        from: payer_token_account.clone(),
+        to: fee_vault.clone(),
+        authority: signer.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, fee_amount)?;
 
     Ok(())
 }
 
+pub fn fee_exemption_discount_bps(fee_controller: &FeeController, metadata_info: &AccountInfo, nft_mint: &Pubkey) -> u16 {
+    if fee_controller.fee_exemptions.is_empty() {
+        return 0;
+    }
+
+    // only genuine metaplex metadata qualifies, anything else simply gets no discount
+    let metadata_program_id = spl_token_metadata::ID;
+    let metadata_seeds = &[
+        METADATA_PREFIX,
+        metadata_program_id.as_ref(),
+        nft_mint.as_ref(),
+    ];
+    let (metaplex_metadata_pda, _bump) = Pubkey::find_program_address(metadata_seeds, &metadata_program_id);
+    if metaplex_metadata_pda != metadata_info.key() {
+        return 0;
+    }
+    let metadata = match deser_metadata(metadata_info, false) {
+        Ok(metadata) => metadata,
+        Err(_) => return 0,
+    };
+    let creators_vec = match metadata.data.creators.as_ref() {
+        Some(creators_vec) => creators_vec,
+        None => return 0,
+    };
+
+    // best matching verified creator wins
+    fee_controller.fee_exemptions
+        .iter()
+        .filter(|e| creators_vec.iter().any(|c| (c.address == e.creator_address) && c.verified))
+        .map(|e| e.discount_bps)
+        .max()
+        .unwrap_or(0)
+}
+
+fn stage_fee_schedule<T: Copy + PartialOrd + Default>(fees: &mut Vec<T>, pending_fees: &mut Vec<T>, new_fees: Vec<T>) -> bool {
+    if fees.len() < MAX_INSTRUCTIONS {
+        fees.resize(MAX_INSTRUCTIONS, T::default());
+    }
+    let mut has_increase = false;
+    for (fee, new_fee) in fees.iter_mut().zip(new_fees.iter()) {
+        if *new_fee <= *fee {
+            *fee = *new_fee;
+        } else {
+            has_increase = true;
+        }
+    }
+    *pending_fees = if has_increase { new_fees } else { Vec::new() };
+    has_increase
+}
+
+fn apply_bps(amount: u64, bps: u64) -> u64 {
+    (amount as u128 * bps as u128 / BPS_DENOMINATOR as u128) as u64
+}
+
 // custom errors
 #[error]
 pub enum ErrorCode {
@@ -1694,6 +4555,71 @@ pub enum ErrorCode {
     InvalidFeeRxAddress,
     #[msg("Invalid fee mod address")]
     InvalidFeeModAddress,
+    // no longer returned, kept so the codes after it stay stable
     #[msg("Invalid instruction name")]
     InvalidInstructionName,
+    #[msg("Invalid fee instruction")]
+    InvalidFeeInstruction,
+    #[msg("Invalid fee schedule")]
+    InvalidFeeSchedule,
+    #[msg("Invalid fee authority")]
+    InvalidFeeAuthority,
+    #[msg("No pending fee authority")]
+    NoPendingFeeAuthority,
+    #[msg("Invalid fee receivers")]
+    InvalidFeeReceivers,
+    #[msg("Invalid fee override")]
+    InvalidFeeOverride,
+    #[msg("Max claim fee exceeded")]
+    MaxClaimFeeExceeded,
+    #[msg("Invalid protocol fee account")]
+    InvalidProtocolFeeAccount,
+    #[msg("Invalid fee vault")]
+    InvalidFeeVault,
+    #[msg("No pending fees")]
+    NoPendingFees,
+    #[msg("Pending fees are not yet due")]
+    PendingFeesNotDue,
+    #[msg("Invalid fee exemptions")]
+    InvalidFeeExemptions,
+    #[msg("Proposal was made for a previous multisig.")]
+    StaleProposal,
+    #[msg("Proposal voting window has closed.")]
+    ProposalExpired,
+    #[msg("Proposal voting window is still open.")]
+    ProposalNotExpired,
+    #[msg("Execution timelock has not elapsed.")]
+    ExecutionTimelockActive,
+    #[msg("Councillor has not approved this proposal.")]
+    ApprovalNotFound,
+    #[msg("Proposal content hash mismatch.")]
+    ProposalHashMismatch,
+    #[msg("Invalid custom instruction.")]
+    InvalidCustomInstruction,
+    #[msg("Custom instruction accounts do not match proposal.")]
+    CustomInstructionAccountMismatch,
+    #[msg("Connection nft still matches a verified creator.")]
+    ConnectionStillVerified,
+    #[msg("Stream still has connections or unclaimed earnings.")]
+    StreamHasOpenConnections,
+    #[msg("Councillor role lacks permission.")]
+    MissingRolePermission,
+    #[msg("Invalid role config.")]
+    InvalidRoleConfig,
+    #[msg("Dao is paused.")]
+    DaoPaused,
+    #[msg("Protocol is paused.")]
+    ProtocolPaused,
+    #[msg("Invalid global config")]
+    InvalidGlobalConfig,
+    #[msg("Invalid treasury mint.")]
+    InvalidTreasuryMint,
+    #[msg("Invalid dao name.")]
+    InvalidDaoName,
+    #[msg("Invalid dao metadata.")]
+    InvalidDaoMetadata,
+    #[msg("Invalid governance config.")]
+    InvalidGovernanceConfig,
+    #[msg("Proposal execution window has closed.")]
+    ExecutionWindowClosed,
 }
\ No newline at end of file
@@ -1,7 +1,14 @@
 pub const MIN_ACCOUNT_LEN: usize = 9;
 pub const MAX_NUM_COUNCILLORS: usize = 11;
 pub const MAX_NUM_VERIFIED_CREATOR_ADDRESSES: usize = 50;
-pub const MAX_INSTRUCTIONS: usize = 30;
+pub const MAX_INSTRUCTIONS: usize = 40;
+pub const MAX_NUM_FEE_RECEIVERS: usize = 5;
+pub const MAX_NUM_FEE_EXEMPTIONS: usize = 10;
+pub const MAX_CUSTOM_INSTRUCTION_ACCOUNTS: usize = 16;
+pub const MAX_CUSTOM_INSTRUCTION_DATA_LEN: usize = 512;
+pub const NUM_COUNCILLOR_ROLES: usize = 3;
+pub const MAX_DAO_NAME_LEN: usize = 32;
+pub const MAX_DAO_URI_LEN: usize = 128;
 
 pub const MAX_SUBSCRIPTION_ACCOUNT_LEN: usize = MIN_ACCOUNT_LEN
     + 32 // owner_address
@@ -11,27 +18,106 @@ pub const MAX_SUBSCRIPTION_ACCOUNT_LEN: usize = MIN_ACCOUNT_LEN
     + 8 // subscription_timestamp
     + 16 // total_earned
     + 16 // total_claimed
+    + 8 // earned_per_connection_snapshot
     + 8; // last_update_timestamp
 
+pub const MAX_DAO_METADATA_LEN: usize = 4 + MAX_DAO_NAME_LEN // display_name
+    + 4 + MAX_DAO_URI_LEN // image_uri
+    + 4 + MAX_DAO_URI_LEN // website_uri
+    + 32; // description_hash
+
 pub const MAX_DAO_ACCOUNT_LEN: usize = MIN_ACCOUNT_LEN
-    + (32 * MAX_NUM_COUNCILLORS) // owners
+    + 32 // creator
+    + 4 + MAX_DAO_NAME_LEN // name
+    + MAX_DAO_METADATA_LEN // metadata
+    + 4 + (32 * MAX_NUM_COUNCILLORS) // councillors
     + 8 // approval_threshold
-    + (1 * MAX_NUM_COUNCILLORS) // proposal_signers
-    + 1 // proposal_is_active
-    + 4 // proposal_type
-    + (32 * MAX_NUM_COUNCILLORS) // proposed_councillors
+    + 4 + MAX_NUM_COUNCILLORS // councillor_roles
+    + 4 + (2 * NUM_COUNCILLOR_ROLES) // role_permissions
+    + 4 + (8 * NUM_COUNCILLOR_ROLES) // role_approval_thresholds
+    + 8 // multisig_nonce
+    + 8 // num_proposals
+    + 8 // proposal_voting_window_seconds
+    + 8 // execution_delay_seconds
+    + 32 // guardian
+    + 1 // paused
+    + 8; // num_streams
+
+pub const MAX_PROPOSAL_PARAMS_LEN: usize = 4 + (32 * MAX_NUM_COUNCILLORS) // proposed_councillors
     + 8 // proposed_approval_threshold
     + 32 // proposed_deactivation_stream
     + 8 // proposed_withdrawal_amount
     + 32 // proposed_withdrawal_receiver_owner
     + 32 // proposed_withdrawal_stream
-    + 8; // num_streams
+    + 8 // proposed_voting_window_seconds
+    + 8 // proposed_execution_delay_seconds
+    + 32 // proposed_guardian
+    + 32 // proposed_instruction_program_id
+    + 4 + ((32 + 1 + 1) * MAX_CUSTOM_INSTRUCTION_ACCOUNTS) // proposed_instruction_accounts
+    + 4 + MAX_CUSTOM_INSTRUCTION_DATA_LEN // proposed_instruction_data
+    + 32 // proposed_stream
+    + 8 // proposed_stream_rate
+    + 32 // proposed_creator_address
+    + 32 // proposed_rent_receiver
+    + 32 // proposed_treasury_mint
+    + MAX_DAO_METADATA_LEN // proposed_metadata
+    + 4 + MAX_NUM_COUNCILLORS // proposed_councillor_roles
+    + 4 + (2 * NUM_COUNCILLOR_ROLES) // proposed_role_permissions
+    + 4 + (8 * NUM_COUNCILLOR_ROLES); // proposed_role_approval_thresholds
+
+pub const MAX_PROPOSAL_ACCOUNT_LEN: usize = MIN_ACCOUNT_LEN
+    + 32 // dao_address
+    + 8 // proposal_index
+    + 32 // proposer
+    + 1 // proposal_type
+    + MAX_PROPOSAL_PARAMS_LEN // params
+    + 32 // content_hash
+    + 8 // multisig_nonce
+    + 8 // created_timestamp
+    + 8 // voting_deadline_timestamp
+    + 4 + (1 * MAX_NUM_COUNCILLORS) // proposal_signers
+    + 1 // status
+    + 8; // threshold_reached_timestamp
 
 pub const MAX_FEE_CONTROLLER_ACCOUNT_LEN: usize = MIN_ACCOUNT_LEN
-        + (8 * MAX_INSTRUCTIONS); // proposal_signers
+        + 4 + (32 * MAX_NUM_COUNCILLORS) // fee_authorities
+        + 8 // fee_approval_threshold
+        + 4 + (32 * MAX_NUM_COUNCILLORS) // pending_fee_authorities
+        + 8 // pending_fee_approval_threshold
+        + 4 + ((32 + 2) * MAX_NUM_FEE_RECEIVERS) // fee_receivers
+        + 4 + (8 * MAX_INSTRUCTIONS) // fees
+        + 8 // fee_increase_delay_seconds
+        + 4 + (8 * MAX_INSTRUCTIONS) // pending_fees
+        + 4 + (8 * MAX_INSTRUCTIONS) // pending_token_fees
+        + 8 // pending_fees_effective_timestamp
+        + 1 + 8 // pending_fee_increase_delay_seconds
+        + 1 + 2 // pending_claim_fee_bps
+        + 1 + 1 + 32 // pending_fee_mint
+        + 1 + 32 // fee_mint
+        + 4 + (8 * MAX_INSTRUCTIONS) // token_fees
+        + 4 + (8 * MAX_INSTRUCTIONS) // fees_collected
+        + 4 + (8 * MAX_INSTRUCTIONS) // token_fees_collected
+        + 4 + (8 * MAX_INSTRUCTIONS) // fee_tx_count
+        + 8 // fee_stats_reset_timestamp
+        + 4 + ((32 + 2) * MAX_NUM_FEE_EXEMPTIONS) // fee_exemptions
+        + 2 // claim_fee_bps
+        + 32; // protocol_fee_owner
+
+pub const MAX_FEE_OVERRIDE_ACCOUNT_LEN: usize = MIN_ACCOUNT_LEN
+        + 32 // dao_address
+        + 4 + (4 * MAX_INSTRUCTIONS) // fee_bps
+        + 4 + (4 * MAX_INSTRUCTIONS) // pending_fee_bps
+        + 8; // pending_fees_effective_timestamp
+
+pub const MAX_GLOBAL_CONFIG_ACCOUNT_LEN: usize = MIN_ACCOUNT_LEN
+        + 1 // max_decimals
+        + 8 // max_stream_rate
+        + 8 // max_connections_per_stream
+        + 1; // is_paused
 
 pub const MAX_STREAM_ACCOUNT_LEN: usize = MIN_ACCOUNT_LEN
         + 32 // dao_address
+        + 8 // stream_index
         + 32 // token_mint_address
         + 32 // token_pool_address
         + (32 * MAX_NUM_VERIFIED_CREATOR_ADDRESSES)  // verified_creator_addresses
@@ -40,6 +126,7 @@ pub const MAX_STREAM_ACCOUNT_LEN: usize = MIN_ACCOUNT_LEN
         + 8 // num_subscribers
         + 16 // total_streamed
         + 16 // total_claimed
+        + 8 // earned_per_connection
         + 8; // last_update_timestamp
 
 pub const MAX_STAKE_ACCOUNT_LEN: usize = MIN_ACCOUNT_LEN